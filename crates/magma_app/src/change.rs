@@ -0,0 +1,379 @@
+use std::{
+    any::{Any, TypeId, type_name},
+    collections::{HashMap, HashSet},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use magma_ecs::entities::Entity;
+
+use crate::{World, error::ChangeError};
+
+/// A point in time used for change detection. The tick is advanced every time the [`App`](crate::App) applies commands.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Tick(u64);
+
+impl Tick {
+    /// Get the current tick of the world. Changes recorded afterwards are newer than the returned tick.
+    pub fn now(world: &World) -> Self {
+        world
+            .get_resource::<ChangeTicks>()
+            .map_or_else(|_| Self::default(), |ticks| ticks.tick)
+    }
+}
+
+/// The ticks at which a component has been added and last changed.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ComponentTicks {
+    added: Tick,
+    changed: Tick,
+}
+
+impl ComponentTicks {
+    /// Get the tick the component has been added at.
+    pub fn added(&self) -> Tick {
+        self.added
+    }
+
+    /// Get the tick the component has last been changed at.
+    pub fn changed(&self) -> Tick {
+        self.changed
+    }
+}
+
+/// Records the changes of one tracked type.
+type Tracker = fn(&World, &mut ChangeTicks);
+
+/**
+Resource containing the current [`Tick`] and the [`ComponentTicks`] of all tracked components. It is added by the [`App`](crate::App).
+
+Components are tracked with [`App::track_changes`](crate::App::track_changes) and resources with [`App::add_tracked_resource`](crate::App::add_tracked_resource).
+When the app applies commands, it advances the tick and records the changes reported by the [`World`] since the last time.
+Changes made during a schedule are therefore visible to [`Added`] and [`Changed`] after the schedule has finished.
+*/
+#[derive(Default)]
+pub struct ChangeTicks {
+    tick: Tick,
+    trackers: Vec<Tracker>,
+    components: HashMap<TypeId, HashMap<Entity, ComponentTicks>>,
+}
+
+impl ChangeTicks {
+    /**
+    Get the [`ComponentTicks`] of the component `C` of `entity`. Returns `None`, if the entity doesn't have the component.
+
+    # Errors
+
+    Returns an error, when `C` isn't tracked.
+    */
+    pub fn get<C: Any + Send + Sync>(
+        &self,
+        entity: Entity,
+    ) -> Result<Option<ComponentTicks>, ChangeError> {
+        Ok(self.of::<C>()?.get(&entity).copied())
+    }
+
+    fn of<C: Any + Send + Sync>(&self) -> Result<&HashMap<Entity, ComponentTicks>, ChangeError> {
+        self.components
+            .get(&TypeId::of::<C>())
+            .ok_or_else(not_tracked::<C>)
+    }
+
+    pub(crate) fn track<C: Any + Send + Sync>(&mut self) {
+        if self.components.contains_key(&TypeId::of::<C>()) {
+            return;
+        }
+        self.components.insert(TypeId::of::<C>(), HashMap::new());
+        self.trackers.push(track_component::<C>);
+    }
+
+    pub(crate) fn track_resource<R: Any + Send + Sync>(&mut self) {
+        self.trackers.push(track_resource::<R>);
+    }
+}
+
+/// Advance the tick and record the changes of all tracked types. This is done by the [`App`](crate::App) after applying commands.
+pub(crate) fn update_ticks(world: &World) {
+    let mut ticks = world.get_resource_mut::<ChangeTicks>().unwrap();
+    ticks.tick.0 += 1;
+    for tracker in ticks.trackers.clone() {
+        tracker(world, &mut ticks);
+    }
+}
+
+/// Record the added, changed and removed components of type `C` reported by the world.
+fn track_component<C: Any + Send + Sync>(world: &World, ticks: &mut ChangeTicks) {
+    let tick = ticks.tick;
+    let components = ticks.components.get_mut(&TypeId::of::<C>()).unwrap();
+    // the queries only fail, when the component isn't registered, so there are no changes
+    if let Ok(added) = world.query_added::<(C,)>() {
+        for entity in added {
+            components.insert(
+                entity.into(),
+                ComponentTicks {
+                    added: tick,
+                    changed: tick,
+                },
+            );
+        }
+    }
+    if let Ok(changed) = world.query_changed::<(C,)>() {
+        for entity in changed {
+            components
+                .entry(entity.into())
+                .or_insert(ComponentTicks {
+                    added: tick,
+                    changed: tick,
+                })
+                .changed = tick;
+        }
+    }
+    let Ok(removed) = world.query_removed::<(C,)>() else {
+        return;
+    };
+    if removed.is_empty() {
+        return;
+    }
+    // the component may have been added again since it has been removed
+    let contained: HashSet<Entity> = world
+        .query::<(C,)>()
+        .map(|entities| entities.iter().map(Entity::from).collect())
+        .unwrap_or_default();
+    for entity in removed {
+        let entity = Entity::from(entity);
+        if !contained.contains(&entity) {
+            components.remove(&entity);
+        }
+    }
+}
+
+/// Record the tick, at which the [`Tracked`] resource `R` has been changed.
+fn track_resource<R: Any + Send + Sync>(world: &World, ticks: &mut ChangeTicks) {
+    if let Ok(mut resource) = world.get_resource_mut::<Tracked<R>>() {
+        if resource.pending {
+            resource.changed = ticks.tick;
+            resource.pending = false;
+        }
+        resource.recorded = ticks.tick;
+    }
+}
+
+/**
+Wraps a resource and tracks when it has been changed. Every mutable access marks the resource as changed.
+Add it with [`App::add_tracked_resource`](crate::App::add_tracked_resource), so its changes are recorded.
+
+Like the changes of components, changes of the resource are visible after the schedule making them has finished.
+[`Tracked::is_changed`] reports the changes made during the last schedule. To find out, whether the resource changed since their last run,
+systems store the [`Tick`] of their last run and use [`Tracked::is_changed_since`].
+
+# Example
+
+```
+use magma_app::{App, World, change::{Tick, Tracked}, schedule::Update, system::SystemConfig};
+
+struct Score(u32);
+
+let mut app = App::new();
+app.add_tracked_resource(Score(0));
+app.add_system::<Update>(SystemConfig::with_state(
+    "show_score",
+    Tick::default(),
+    |world: &World, last_run: &mut Tick| {
+        if world.get_resource::<Tracked<Score>>().unwrap().is_changed_since(*last_run) {
+            // only redraw the score, when it changed
+        }
+        *last_run = Tick::now(world);
+    },
+))
+.unwrap();
+```
+*/
+#[derive(Clone, Copy, Debug)]
+pub struct Tracked<T> {
+    value: T,
+    /// the tick of the last recorded change
+    changed: Tick,
+    /// the tick, at which changes have last been recorded
+    recorded: Tick,
+    /// whether the value has been changed since the last recorded change
+    pending: bool,
+}
+
+impl<T> Tracked<T> {
+    /// Wrap a value. It counts as changed, when its changes are first recorded.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            changed: Tick::default(),
+            recorded: Tick::default(),
+            pending: true,
+        }
+    }
+
+    /// Returns `true` if a change of the value has been recorded after `tick`.
+    pub fn is_changed_since(&self, tick: Tick) -> bool {
+        self.changed > tick
+    }
+
+    /// Returns `true` if a change of the value has been recorded, when changes have last been recorded.
+    /// Those are the changes made between the last two times the [`App`](crate::App) applied commands.
+    pub fn is_changed(&self) -> bool {
+        self.changed == self.recorded && self.changed > Tick::default()
+    }
+
+    /// Get mutable access to the value without marking it as changed.
+    pub fn bypass_change_detection(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Unwrap the value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Default> Default for Tracked<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: PartialEq> PartialEq for Tracked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Tracked<T> {}
+
+impl<T: Hash> Hash for Tracked<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Tracked<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.pending = true;
+        &mut self.value
+    }
+}
+
+/**
+Filter for entities, whose component `T` has been added after a [`Tick`]. `T` has to be tracked, see [`App::track_changes`](crate::App::track_changes).
+
+# Example
+
+```
+use magma_app::{App, World, change::{Added, Tick}, schedule::Update, system::SystemConfig};
+
+struct Enemy;
+
+let mut app = App::new();
+app.world.register_component::<Enemy>();
+app.track_changes::<Enemy>();
+app.add_system::<Update>(SystemConfig::with_state(
+    "greet_enemies",
+    Tick::default(),
+    |world: &World, last_run: &mut Tick| {
+        for enemy in Added::<Enemy>::since(world, *last_run).unwrap() {
+            // greet the new enemy
+        }
+        *last_run = Tick::now(world);
+    },
+))
+.unwrap();
+```
+*/
+pub struct Added<T>(PhantomData<fn() -> T>);
+
+impl<T: Any + Send + Sync> Added<T> {
+    /**
+    Returns `true` if the component of `entity` has been added after `tick`.
+
+    # Errors
+
+    Returns an error, when `T` isn't tracked.
+    */
+    pub fn matches(world: &World, entity: Entity, tick: Tick) -> Result<bool, ChangeError> {
+        Ok(component_ticks::<T>(world, entity)?.is_some_and(|ticks| ticks.added > tick))
+    }
+
+    /**
+    Get all entities, whose component has been added after `tick`.
+
+    # Errors
+
+    Returns an error, when `T` isn't tracked.
+    */
+    pub fn since(world: &World, tick: Tick) -> Result<Vec<Entity>, ChangeError> {
+        filter_entities::<T>(world, |ticks| ticks.added > tick)
+    }
+}
+
+/// Filter for entities, whose component `T` has been added or changed after a [`Tick`]. `T` has to be tracked, see [`App::track_changes`](crate::App::track_changes).
+pub struct Changed<T>(PhantomData<fn() -> T>);
+
+impl<T: Any + Send + Sync> Changed<T> {
+    /**
+    Returns `true` if the component of `entity` has been added or changed after `tick`.
+
+    # Errors
+
+    Returns an error, when `T` isn't tracked.
+    */
+    pub fn matches(world: &World, entity: Entity, tick: Tick) -> Result<bool, ChangeError> {
+        Ok(component_ticks::<T>(world, entity)?.is_some_and(|ticks| ticks.changed > tick))
+    }
+
+    /**
+    Get all entities, whose component has been added or changed after `tick`.
+
+    # Errors
+
+    Returns an error, when `T` isn't tracked.
+    */
+    pub fn since(world: &World, tick: Tick) -> Result<Vec<Entity>, ChangeError> {
+        filter_entities::<T>(world, |ticks| ticks.changed > tick)
+    }
+}
+
+fn component_ticks<T: Any + Send + Sync>(
+    world: &World,
+    entity: Entity,
+) -> Result<Option<ComponentTicks>, ChangeError> {
+    world
+        .get_resource::<ChangeTicks>()
+        .map_err(|_| not_tracked::<T>())?
+        .get::<T>(entity)
+}
+
+/// Get the entities, whose ticks of the component `T` match `filter`.
+fn filter_entities<T: Any + Send + Sync>(
+    world: &World,
+    filter: impl Fn(&ComponentTicks) -> bool,
+) -> Result<Vec<Entity>, ChangeError> {
+    Ok(world
+        .get_resource::<ChangeTicks>()
+        .map_err(|_| not_tracked::<T>())?
+        .of::<T>()?
+        .iter()
+        .filter(|(_, ticks)| filter(ticks))
+        .map(|(entity, _)| *entity)
+        .collect())
+}
+
+fn not_tracked<T>() -> ChangeError {
+    ChangeError::NotTracked {
+        component: type_name::<T>(),
+    }
+}
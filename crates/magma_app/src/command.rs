@@ -0,0 +1,178 @@
+use std::{
+    any::{Any, TypeId},
+    cell::Cell,
+    sync::Mutex,
+};
+
+use magma_ecs::entities::{ComponentSet, Entity};
+use tracing::{info_span, warn};
+
+use crate::{World, hierarchy::despawn_recursive, hook};
+
+/// A deferred change to the [`World`].
+pub type Command = Box<dyn FnOnce(&World) + Send>;
+
+/// Schedule name and index of the system recording commands. Used to apply them in a deterministic order.
+type CommandKey = Option<(&'static str, usize)>;
+
+thread_local! {
+    static CURRENT_SYSTEM: Cell<CommandKey> = const { Cell::new(None) };
+}
+
+/// Run `f` with the system identified by `schedule` and `index` as the current system of the thread.
+pub(crate) fn with_system<R>(schedule: &'static str, index: usize, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT_SYSTEM.replace(Some((schedule, index)));
+    let result = f();
+    CURRENT_SYSTEM.set(previous);
+    result
+}
+
+/// Resource containing the recorded commands until the next sync point. It is added by the [`App`](crate::App).
+#[derive(Default)]
+pub(crate) struct CommandQueue(Mutex<Vec<(CommandKey, Vec<Command>)>>);
+
+/// Apply all recorded commands. Commands of different systems are applied in order of the systems in their schedule.
+pub(crate) fn apply_commands(world: &World) {
+    let mut queued = std::mem::take(
+        &mut *world
+            .get_resource::<CommandQueue>()
+            .unwrap()
+            .0
+            .lock()
+            .unwrap(),
+    );
+    if queued.is_empty() {
+        return;
+    }
+    let _span = info_span!("apply_commands").entered();
+    queued.sort_by_key(|(key, _)| *key);
+    for command in queued.into_iter().flat_map(|(_, commands)| commands) {
+        command(world);
+    }
+}
+
+/**
+Records structural changes to the [`World`], which are applied at the next sync point.
+
+The [`App`](crate::App) applies commands after every schedule, after the event systems have run and after every state transition schedule.
+Commands recorded by one system are applied in the order they have been recorded. Commands of different systems are applied in the order of the systems in their schedule,
+so the result doesn't depend on which parallel system finished first.
+
+Create the [`Commands`] at the start of the system. They are queued when dropped. [`Commands`] can be shared with parallel iterators.
+Component hooks (see [`App::add_component_hook`](crate::App::add_component_hook)) run when the commands are applied.
+If the [`World`] doesn't belong to an [`App`](crate::App), the commands are applied immediately when dropped.
+
+# Example
+
+```
+use magma_app::{App, World, command::Commands, schedule::Update};
+
+struct Health(u32);
+struct Dead;
+
+let mut app = App::new();
+app.world.register_component::<Health>();
+app.world.register_component::<Dead>();
+app.add_systems::<Update>(&[(mark_dead, "mark_dead", &[])]).unwrap();
+
+fn mark_dead(world: &World) {
+    let commands = Commands::new(world);
+    world
+        .query::<(Health,)>()
+        .unwrap()
+        .iter()
+        .filter(|entity| entity.get_component::<Health>().unwrap().0 == 0)
+        .for_each(|entity| commands.insert(entity.into(), (Dead,)));
+}
+```
+*/
+pub struct Commands<'w> {
+    world: &'w World,
+    key: CommandKey,
+    commands: Mutex<Vec<Command>>,
+}
+
+impl<'w> Commands<'w> {
+    /// Create new [`Commands`] for the [`World`].
+    pub fn new(world: &'w World) -> Self {
+        Self {
+            world,
+            key: CURRENT_SYSTEM.get(),
+            commands: Mutex::new(vec![]),
+        }
+    }
+
+    /// Queue a custom command.
+    pub fn add(&self, command: impl FnOnce(&World) + Send + 'static) {
+        self.commands.lock().unwrap().push(Box::new(command));
+    }
+
+    /// Create an entity with the specified components.
+    pub fn spawn<C: ComponentSet + Send + 'static>(&self, components: C) {
+        self.add(move |world| match world.create_entity(components) {
+            Ok(entity) => hook::spawned(world, entity, &C::type_ids()),
+            Err(error) => warn!("failed to spawn entity: {error}"),
+        });
+    }
+
+    /// Delete an entity with all of its components and descendants. See [`despawn_recursive`].
+    pub fn despawn(&self, entity: Entity) {
+        self.add(move |world| {
+            if let Err(error) = despawn_recursive(world, entity) {
+                warn!("failed to despawn entity {}: {error}", entity.id());
+            }
+        });
+    }
+
+    /// Add components to an entity. Components the entity already has are replaced.
+    pub fn insert<C: ComponentSet + Send + 'static>(&self, entity: Entity, components: C) {
+        self.add(move |world| {
+            let before = hook::contained(world, entity, &C::type_ids());
+            if let Err(error) = world.assign_components(components, entity) {
+                warn!(
+                    "failed to insert components into entity {}: {error}",
+                    entity.id()
+                );
+            } else {
+                hook::inserted(world, entity, &before);
+            }
+        });
+    }
+
+    /// Remove the component of type `C` from an entity and drop its data.
+    pub fn remove<C: Any + Send + Sync>(&self, entity: Entity) {
+        self.add(move |world| {
+            hook::removing(world, entity, TypeId::of::<C>());
+            if let Err(error) = world.purge_component::<C>(entity) {
+                warn!(
+                    "failed to remove component from entity {}: {error}",
+                    entity.id()
+                );
+            }
+        });
+    }
+
+    /// Get the amount of recorded commands.
+    pub fn len(&self) -> usize {
+        self.commands.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no commands have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Drop for Commands<'_> {
+    fn drop(&mut self) {
+        let commands = std::mem::take(self.commands.get_mut().unwrap());
+        if commands.is_empty() {
+            return;
+        }
+        match self.world.get_resource::<CommandQueue>() {
+            Ok(queue) => queue.0.lock().unwrap().push((self.key, commands)),
+            // without an app there is no sync point, which would apply the commands
+            Err(_) => commands.into_iter().for_each(|command| command(self.world)),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::{any::Any, sync::Mutex, time::Duration};
+
+use crate::{World, time::Time};
+
+/// Returns `true` if a resource of type `R` exists.
+pub fn resource_exists<R: Any + Send + Sync>(world: &World) -> bool {
+    world.get_resource::<R>().is_ok()
+}
+
+/// Returns a condition, which is `true` if a resource of type `R` exists and is equal to `value`.
+pub fn resource_equals<R: Any + Send + Sync + PartialEq>(
+    value: R,
+) -> impl Fn(&World) -> bool + Send + Sync {
+    move |world| {
+        world
+            .get_resource::<R>()
+            .is_ok_and(|resource| *resource == value)
+    }
+}
+
+/**
+Returns a condition, which is `true` once every time `interval` has passed on the [`Time`] clock.
+
+# Example
+
+```
+use std::time::Duration;
+use magma_app::{App, World, condition::on_timer, schedule::Update, system::SystemConfig};
+
+let mut app = App::new();
+app.add_system::<Update>(
+    SystemConfig::new("autosave", autosave).run_if(on_timer(Duration::from_secs(60))),
+)
+.unwrap();
+
+fn autosave(_world: &World) {}
+```
+*/
+pub fn on_timer(interval: Duration) -> impl Fn(&World) -> bool + Send + Sync {
+    let next = Mutex::new(interval);
+    move |world| {
+        let elapsed = world.get_resource::<Time>().unwrap().elapsed();
+        let mut next = next.lock().unwrap();
+        if elapsed < *next {
+            return false;
+        }
+        *next += interval;
+        // skip intervals, which have been missed completely
+        if *next <= elapsed {
+            *next = elapsed + interval;
+        }
+        true
+    }
+}
+
+/// Returns a condition, which is `true` if `condition` is `false`.
+pub fn not(
+    condition: impl Fn(&World) -> bool + Send + Sync,
+) -> impl Fn(&World) -> bool + Send + Sync {
+    move |world| !condition(world)
+}
@@ -0,0 +1,315 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use crate::{
+    App, World,
+    condition::on_timer,
+    module::Module,
+    schedule::{Last, Shutdown},
+    system::SystemConfig,
+    time::Time,
+};
+use tracing::info;
+
+/// The name of the frame time diagnostic in milliseconds.
+pub const FRAME_TIME: &str = "frame_time";
+/// The name of the frames per second diagnostic.
+pub const FPS: &str = "fps";
+/// The prefix of the event count diagnostics. It is followed by the name of the event type.
+pub const EVENT_COUNT_PREFIX: &str = "event/";
+/// The prefix of the system execution time diagnostics in milliseconds. It is followed by `<schedule>/<system>`.
+pub const SYSTEM_TIME_PREFIX: &str = "system/";
+
+/// The recorded history of a single measurement.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+    history: VecDeque<f64>,
+    max_history: usize,
+    sum: f64,
+}
+
+impl Diagnostic {
+    /// Create a new [`Diagnostic`], which keeps the specified amount of measurements.
+    pub fn new(max_history: usize) -> Self {
+        Self {
+            history: VecDeque::with_capacity(max_history),
+            max_history,
+            sum: 0.0,
+        }
+    }
+
+    /// Add a measurement. The oldest measurement is dropped, if the history is full.
+    pub fn add_measurement(&mut self, value: f64) {
+        if self.max_history == 0 {
+            return;
+        }
+        if self.history.len() == self.max_history {
+            self.sum -= self.history.pop_front().unwrap_or_default();
+        }
+        self.history.push_back(value);
+        self.sum += value;
+    }
+
+    /// Get the latest measurement.
+    pub fn latest(&self) -> Option<f64> {
+        self.history.back().copied()
+    }
+
+    /// Get the rolling average of the history.
+    pub fn average(&self) -> Option<f64> {
+        (!self.history.is_empty()).then(|| self.sum / self.history.len() as f64)
+    }
+
+    /// Iterate over the history, oldest first.
+    pub fn history(&self) -> impl Iterator<Item = f64> + '_ {
+        self.history.iter().copied()
+    }
+}
+
+/**
+Resource containing all recorded [`Diagnostic`]s by name. It is added by the [`DiagnosticsModule`].
+
+# Example
+
+```
+use magma_app::{World, diagnostic::{Diagnostics, FPS}};
+
+fn show_fps(world: &World) {
+    let diagnostics = world.get_resource::<Diagnostics>().unwrap();
+    if let Some(fps) = diagnostics.get(FPS).and_then(|fps| fps.average()) {
+        // display the fps
+    }
+}
+```
+*/
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostics {
+    diagnostics: BTreeMap<String, Diagnostic>,
+    max_history: usize,
+}
+
+impl Diagnostics {
+    /// Create a new [`Diagnostics`] resource. Every [`Diagnostic`] keeps the specified amount of measurements.
+    pub fn new(max_history: usize) -> Self {
+        Self {
+            diagnostics: BTreeMap::new(),
+            max_history,
+        }
+    }
+
+    /// Get a [`Diagnostic`] by name.
+    pub fn get(&self, name: &str) -> Option<&Diagnostic> {
+        self.diagnostics.get(name)
+    }
+
+    /// Add a measurement to the [`Diagnostic`] with the specified name. It is created, if it doesn't exist.
+    pub fn add_measurement(&mut self, name: &str, value: f64) {
+        if let Some(diagnostic) = self.diagnostics.get_mut(name) {
+            diagnostic.add_measurement(value);
+        } else {
+            let mut diagnostic = Diagnostic::new(self.max_history);
+            diagnostic.add_measurement(value);
+            self.diagnostics.insert(name.to_owned(), diagnostic);
+        }
+    }
+
+    /// Iterate over all [`Diagnostic`]s sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Diagnostic)> {
+        self.diagnostics
+            .iter()
+            .map(|(name, diagnostic)| (name.as_str(), diagnostic))
+    }
+
+    /// Format the history of all diagnostics as CSV with the columns `name`, `sample` and `value`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("name,sample,value\n");
+        for (name, diagnostic) in self.iter() {
+            for (sample, value) in diagnostic.history().enumerate() {
+                writeln!(csv, "\"{}\",{sample},{value}", name.replace('"', "\"\"")).unwrap();
+            }
+        }
+        csv
+    }
+
+    /// Format all diagnostics as a JSON object, containing the latest value, the average and the history of every diagnostic.
+    pub fn to_json(&self) -> String {
+        let diagnostics: Vec<String> = self
+            .iter()
+            .map(|(name, diagnostic)| {
+                let history: Vec<String> = diagnostic.history().map(json_number).collect();
+                format!(
+                    "\"{}\":{{\"latest\":{},\"average\":{},\"history\":[{}]}}",
+                    name.replace('\\', "\\\\").replace('"', "\\\""),
+                    diagnostic.latest().map_or("null".to_owned(), json_number),
+                    diagnostic.average().map_or("null".to_owned(), json_number),
+                    history.join(",")
+                )
+            })
+            .collect();
+        format!("{{{}}}", diagnostics.join(","))
+    }
+
+    /**
+    Write the diagnostics to a file in the specified format.
+
+    # Errors
+
+    Returns an error, when the file can't be written.
+    */
+    pub fn write(&self, path: impl AsRef<Path>, format: DumpFormat) -> io::Result<()> {
+        let content = match format {
+            DumpFormat::Csv => self.to_csv(),
+            DumpFormat::Json => self.to_json(),
+        };
+        fs::write(path, content)
+    }
+}
+
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_owned()
+    }
+}
+
+/// The file format of a diagnostics dump.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum DumpFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// Execution times of the systems measured during the current frame.
+#[derive(Default)]
+pub(crate) struct SystemTimings(Mutex<Vec<(&'static str, &'static str, Duration)>>);
+
+impl SystemTimings {
+    pub(crate) fn record(&self, schedule: &'static str, system: &'static str, time: Duration) {
+        self.0.lock().unwrap().push((schedule, system, time));
+    }
+}
+
+/// Settings of the [`DiagnosticsModule`], which are added as a resource.
+#[derive(Clone, PartialEq, Debug)]
+struct DiagnosticsSettings {
+    dump: Option<(PathBuf, DumpFormat)>,
+}
+
+/**
+Module recording diagnostics of the [`App`] every frame into the [`Diagnostics`] resource.
+
+This records the frame time, the frames per second, the amount of events sent per event type and optionally the execution time of every system.
+
+# Example
+
+```
+use std::time::Duration;
+use magma_app::{App, diagnostic::{DiagnosticsModule, DumpFormat}};
+
+let mut app = App::new();
+app.add_module(DiagnosticsModule {
+    log_interval: Some(Duration::from_secs(1)),
+    dump: Some(("diagnostics.json".into(), DumpFormat::Json)),
+    ..Default::default()
+});
+```
+*/
+#[derive(Clone, PartialEq, Debug)]
+pub struct DiagnosticsModule {
+    /// The amount of measurements kept per diagnostic.
+    pub max_history: usize,
+    /// Measure the execution time of every system.
+    pub system_timings: bool,
+    /// Log the averages of all diagnostics in this interval.
+    pub log_interval: Option<Duration>,
+    /// Write the diagnostics to this file, when the [`App`] shuts down.
+    pub dump: Option<(PathBuf, DumpFormat)>,
+}
+
+impl Default for DiagnosticsModule {
+    fn default() -> Self {
+        Self {
+            max_history: 120,
+            system_timings: true,
+            log_interval: None,
+            dump: None,
+        }
+    }
+}
+
+impl Module for DiagnosticsModule {
+    fn setup(self, app: &mut App) {
+        app.world
+            .add_resource(Diagnostics::new(self.max_history))
+            .unwrap();
+        if self.system_timings {
+            app.world.add_resource(SystemTimings::default()).unwrap();
+        }
+        if let Some(interval) = self.log_interval {
+            app.add_system::<Last>(
+                SystemConfig::new("diagnostics_log", log_diagnostics).run_if(on_timer(interval)),
+            )
+            .unwrap();
+        }
+        app.world
+            .add_resource(DiagnosticsSettings { dump: self.dump })
+            .unwrap();
+        app.add_system::<Shutdown>(SystemConfig::new("diagnostics_dump", dump_diagnostics))
+            .unwrap();
+    }
+}
+
+/// Record the diagnostics of the frame. This is done by the [`App`] at the end of every frame.
+pub(crate) fn record_frame(world: &World) {
+    let Ok(mut diagnostics) = world.get_resource_mut::<Diagnostics>() else {
+        return;
+    };
+    let delta = world.get_resource::<Time>().unwrap().real_delta();
+    if !delta.is_zero() {
+        diagnostics.add_measurement(FRAME_TIME, delta.as_secs_f64() * 1000.0);
+        diagnostics.add_measurement(FPS, 1.0 / delta.as_secs_f64());
+    }
+    if let Ok(timings) = world.get_resource::<SystemTimings>() {
+        for (schedule, system, time) in timings.0.lock().unwrap().drain(..) {
+            diagnostics.add_measurement(
+                &format!("{SYSTEM_TIME_PREFIX}{schedule}/{system}"),
+                time.as_secs_f64() * 1000.0,
+            );
+        }
+    }
+}
+
+/// Record the amount of events of type `name` sent during the frame.
+pub(crate) fn record_event_count(world: &World, name: &str, count: usize) {
+    if let Ok(mut diagnostics) = world.get_resource_mut::<Diagnostics>() {
+        diagnostics.add_measurement(&format!("{EVENT_COUNT_PREFIX}{name}"), count as f64);
+    }
+}
+
+fn log_diagnostics(world: &World) {
+    let diagnostics = world.get_resource::<Diagnostics>().unwrap();
+    for (name, diagnostic) in diagnostics.iter() {
+        if let Some(average) = diagnostic.average() {
+            info!(target: "magma_app::diagnostic", "{name}: {average:.3}");
+        }
+    }
+}
+
+fn dump_diagnostics(world: &World) -> io::Result<()> {
+    let settings = world.get_resource::<DiagnosticsSettings>().unwrap();
+    match &settings.dump {
+        Some((path, format)) => world
+            .get_resource::<Diagnostics>()
+            .unwrap()
+            .write(path, *format),
+        None => Ok(()),
+    }
+}
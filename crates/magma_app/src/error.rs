@@ -1,3 +1,5 @@
+use std::error::Error as StdError;
+
 use thiserror::Error;
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Error)]
@@ -5,4 +7,76 @@ pub enum ScheduleError {
     /// The schedule requested is not registered
     #[error("attempted to access unregistered schedule")]
     ScheduleNotRegistered,
+    /// The schedule requested is not part of the startup or main schedules
+    #[error("schedule is not part of the startup or main schedules")]
+    ScheduleNotOrdered,
+    /// The system requested is not part of the schedule
+    #[error("attempted to access a system, which is not part of the schedule")]
+    SystemNotFound,
+    /// Multiple systems with the same name have been added to the schedule
+    #[error("system `{system}` has been added to the schedule multiple times")]
+    DuplicateSystem { system: &'static str },
+    /// A system or set depends on a name, which is neither a system nor a set in the schedule
+    #[error("system `{system}` depends on `{dependency}`, which is neither a system nor a set")]
+    UnknownDependency {
+        system: &'static str,
+        dependency: &'static str,
+    },
+    /// The ordering of the schedule contains a cycle
+    #[error("system `{system}` is part of or depends on a dependency cycle")]
+    DependencyCycle { system: &'static str },
+    /// The ordering of event types contains a cycle
+    #[error("the systems of event `{event}` are ordered before themselves")]
+    EventOrderCycle { event: &'static str },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Error)]
+pub enum ModuleError {
+    /// The module conflicts with a module, that has already been added
+    #[error("module `{module}` conflicts with module `{conflict}`")]
+    Conflict {
+        module: &'static str,
+        conflict: &'static str,
+    },
+    /// A module required by the module has not been added
+    #[error("module `{module}` requires module `{dependency}`, which has not been added")]
+    MissingDependency {
+        module: &'static str,
+        dependency: &'static str,
+    },
+    /// The dependencies of the module form a cycle
+    #[error("module `{module}` has a cyclic dependency on module `{dependency}`")]
+    DependencyCycle {
+        module: &'static str,
+        dependency: &'static str,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Error)]
+pub enum HierarchyError {
+    /// The entity doesn't exist
+    #[error("attempted to access an entity, which doesn't exist")]
+    EntityNotFound,
+    /// The new parent is the entity itself or one of its descendants
+    #[error("an entity can't be a child of itself or one of its descendants")]
+    Cycle,
+}
+
+/// An error returned by a system. It is passed to the [`ErrorHandler`](crate::system::ErrorHandler) of the [`App`](crate::App).
+#[derive(Debug, Error)]
+#[error("system `{system}` in schedule `{schedule}` failed: {error}")]
+pub struct SystemError {
+    /// The name of the failing system
+    pub system: &'static str,
+    /// The name of the schedule or event type, which ran the system
+    pub schedule: &'static str,
+    /// The error returned by the system
+    pub error: Box<dyn StdError>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Error)]
+pub enum ChangeError {
+    /// The changes of the component aren't tracked
+    #[error("changes of component `{component}` are not tracked")]
+    NotTracked { component: &'static str },
 }
@@ -0,0 +1,209 @@
+use std::{
+    any::{Any, type_name},
+    marker::PhantomData,
+};
+
+use magma_ecs::error::EventError;
+
+use crate::{World, diagnostic::record_event_count};
+
+/**
+Resource containing the events of type `E` sent during the last two frames.
+
+Every event type registered with [`App::register_event`](crate::App::register_event) gets an [`Events`] resource.
+At the end of every frame the events sent during the frame are moved into it and events older than two frames are dropped.
+This means events can be read from any schedule during the two frames after they have been sent, using an [`EventReader`].
+
+# Example
+
+```
+use magma_app::{App, World, event::{EventReader, Events}, schedule::PostUpdate, system::SystemConfig};
+
+#[derive(Clone)]
+struct Jump;
+
+let mut app = App::new();
+app.register_event::<Jump>();
+app.add_system::<PostUpdate>(SystemConfig::with_state(
+    "count_jumps",
+    EventReader::<Jump>::default(),
+    |world: &World, reader: &mut EventReader<Jump>| {
+        let events = world.get_resource::<Events<Jump>>().unwrap();
+        let jumps = reader.read(&events).count();
+        // do something with the jumps
+    },
+))
+.unwrap();
+```
+*/
+pub struct Events<E> {
+    previous: Vec<E>,
+    current: Vec<E>,
+    /// id of the first event in `previous`
+    start: usize,
+    /// id of the next event to be sent
+    count: usize,
+}
+
+impl<E> Default for Events<E> {
+    fn default() -> Self {
+        Self {
+            previous: vec![],
+            current: vec![],
+            start: 0,
+            count: 0,
+        }
+    }
+}
+
+impl<E> Events<E> {
+    /// Add an event to the current buffer. Prefer [`EventWriter::send`], so event systems are run as well.
+    pub fn send(&mut self, event: E) {
+        self.current.push(event);
+        self.count += 1;
+    }
+
+    /// Swap the buffers and drop the events of the previous buffer. This is done by the [`App`](crate::App) every frame.
+    pub fn update(&mut self) {
+        self.start += self.previous.len();
+        self.previous = std::mem::take(&mut self.current);
+    }
+
+    /// Iterate over all events in both buffers, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &E> {
+        self.previous.iter().chain(&self.current)
+    }
+
+    /// Get the amount of events in both buffers.
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    /// Returns `true` if both buffers are empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Create an [`EventReader`], which only reads events sent after its creation.
+    pub fn reader_from_now(&self) -> EventReader<E> {
+        EventReader {
+            cursor: self.count,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E> Extend<E> for Events<E> {
+    fn extend<T: IntoIterator<Item = E>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|event| self.send(event));
+    }
+}
+
+/**
+Reads events from an [`Events`] resource. Each reader keeps its own cursor, so every event is read at most once per reader.
+
+Readers are usually stored as the state of a system, see [`SystemConfig::with_state`](crate::system::SystemConfig::with_state).
+A reader, which is read less often than every other frame, misses events.
+*/
+pub struct EventReader<E> {
+    /// id of the next unread event
+    cursor: usize,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<E> Default for EventReader<E> {
+    fn default() -> Self {
+        Self {
+            cursor: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E> EventReader<E> {
+    /// Iterate over the events, which haven't been read by this reader yet, and mark them as read.
+    pub fn read<'a>(&mut self, events: &'a Events<E>) -> impl Iterator<Item = &'a E> {
+        let skip = self.cursor.saturating_sub(events.start);
+        self.cursor = events.count;
+        events.iter().skip(skip)
+    }
+
+    /// Get the amount of events, which haven't been read by this reader yet.
+    pub fn len(&self, events: &Events<E>) -> usize {
+        events.count - self.cursor.max(events.start)
+    }
+
+    /// Returns `true` if there are no unread events.
+    pub fn is_empty(&self, events: &Events<E>) -> bool {
+        self.len(events) == 0
+    }
+
+    /// Mark all events as read.
+    pub fn clear(&mut self, events: &Events<E>) {
+        self.cursor = events.count;
+    }
+}
+
+/// Sends events of type `E`. The events run the event systems of `E` and are moved into the [`Events`] resource at the end of the frame.
+pub struct EventWriter<'w, E> {
+    world: &'w World,
+    _marker: PhantomData<fn(E)>,
+}
+
+impl<'w, E: Any + Send + Sync + Clone> EventWriter<'w, E> {
+    /// Create a new [`EventWriter`] for the [`World`].
+    pub fn new(world: &'w World) -> Self {
+        Self {
+            world,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+    Send an event.
+
+    # Errors
+
+    Returns an error, when the event isn't registered.
+    */
+    pub fn send(&self, event: E) -> Result<(), EventError> {
+        self.world.send_event(event)
+    }
+
+    /**
+    Send multiple events.
+
+    # Errors
+
+    Returns an error, when the event isn't registered.
+    */
+    pub fn send_batch(&self, events: impl IntoIterator<Item = E>) -> Result<(), EventError> {
+        events
+            .into_iter()
+            .try_for_each(|event| self.world.send_event(event))
+    }
+}
+
+/**
+How the [`App`](crate::App) runs the systems of different event types. See [`App::set_event_dispatch_mode`](crate::App::set_event_dispatch_mode).
+
+In both modes the ordering declared with [`App::order_event_systems`](crate::App::order_event_systems) is respected.
+*/
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum EventDispatchMode {
+    /// The systems of event types, which aren't ordered relative to each other, run in parallel.
+    #[default]
+    Parallel,
+    /// The systems of one event type run at a time, in the declared order and otherwise in order of registration.
+    /// This makes the order between event types deterministic.
+    Sequential,
+}
+
+/// Move the pending events of type `E` into its [`Events`] resource.
+pub(crate) fn update_events<E: Any + Send + Sync + Clone>(world: &World) {
+    let pending: Vec<E> = world.poll_events::<E>().unwrap().to_vec();
+    record_event_count(world, type_name::<E>(), pending.len());
+    let mut events = world.get_resource_mut::<Events<E>>().unwrap();
+    events.update();
+    events.extend(pending);
+}
@@ -0,0 +1,81 @@
+use std::{
+    num::NonZeroU8,
+    process::{ExitCode, Termination},
+};
+
+/**
+An event, which can be sent by any system to exit the [`App`](crate::App).
+
+The runner finishes the current frame, runs the [`Shutdown`](crate::schedule::Shutdown) schedule and returns the [`AppExit`] from [`App::run`](crate::App::run).
+If multiple [`AppExit`] events are sent in the same frame, errors take precedence.
+
+# Example
+
+```
+use magma_app::{App, AppExit, World, schedule::Update};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+    app.add_systems::<Update>(&[(exit_system, "exit_system", &[])])
+        .unwrap();
+    app.run()
+}
+
+fn exit_system(world: &World) {
+    world.send_event(AppExit::Success).unwrap();
+}
+```
+*/
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum AppExit {
+    /// The [`App`](crate::App) exited without errors.
+    #[default]
+    Success,
+    /// The [`App`](crate::App) exited with an error. This contains the exit code.
+    Error(NonZeroU8),
+}
+
+impl AppExit {
+    /// Returns an [`AppExit::Error`] with the exit code `1`.
+    pub const fn error() -> Self {
+        Self::Error(NonZeroU8::MIN)
+    }
+
+    /// Returns an [`AppExit`] from an exit code. `0` is [`AppExit::Success`].
+    pub const fn from_code(code: u8) -> Self {
+        match NonZeroU8::new(code) {
+            Some(code) => Self::Error(code),
+            None => Self::Success,
+        }
+    }
+
+    /// Returns the exit code of the [`AppExit`].
+    pub const fn code(&self) -> u8 {
+        match self {
+            Self::Success => 0,
+            Self::Error(code) => code.get(),
+        }
+    }
+
+    /// Returns `true` if this is [`AppExit::Success`].
+    pub const fn is_success(&self) -> bool {
+        matches!(self, Self::Success)
+    }
+
+    /// Returns `true` if this is an [`AppExit::Error`].
+    pub const fn is_error(&self) -> bool {
+        matches!(self, Self::Error(_))
+    }
+}
+
+impl From<u8> for AppExit {
+    fn from(code: u8) -> Self {
+        Self::from_code(code)
+    }
+}
+
+impl Termination for AppExit {
+    fn report(self) -> ExitCode {
+        ExitCode::from(self.code())
+    }
+}
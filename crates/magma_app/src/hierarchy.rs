@@ -0,0 +1,256 @@
+use std::any::TypeId;
+
+use magma_ecs::entities::{ComponentSet, Entity};
+use tracing::warn;
+
+use crate::{World, command::Commands, error::HierarchyError, hook};
+
+/**
+Component containing the parent of an entity. It is kept consistent with [`Children`] by the hierarchy functions and can't be changed directly.
+
+# Example
+
+```
+use magma_app::{App, World, command::Commands, hierarchy::Children, schedule::Startup};
+
+struct Ship;
+struct Turret;
+
+let mut app = App::new();
+app.world.register_component::<Ship>();
+app.world.register_component::<Turret>();
+app.add_systems::<Startup>(&[(spawn_ship, "spawn_ship", &[])]).unwrap();
+app.startup();
+
+let ships = app.world.query::<(Ship, Children)>().unwrap();
+assert_eq!(ships[0].get_component::<Children>().unwrap().len(), 2);
+
+fn spawn_ship(world: &World) {
+    let ship = world.create_entity((Ship,)).unwrap();
+    let commands = Commands::new(world);
+    commands.spawn_child(ship, (Turret,));
+    commands.spawn_child(ship, (Turret,));
+}
+```
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Parent(pub(crate) Entity);
+
+impl Parent {
+    /// Get the parent entity.
+    pub fn get(&self) -> Entity {
+        self.0
+    }
+}
+
+/// Component containing the children of an entity in order of insertion. It is kept consistent with [`Parent`] by the hierarchy functions.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Children(pub(crate) Vec<Entity>);
+
+impl Children {
+    /// Iterate over the children.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Get the amount of children.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no children.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if `entity` is a child.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.0.contains(&entity)
+    }
+}
+
+/// Event sent whenever the hierarchy changes. It is registered by the [`App`](crate::App).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum HierarchyEvent {
+    /// `child`, which didn't have a parent before, has been added to `parent`.
+    ChildAdded { child: Entity, parent: Entity },
+    /// `child` has been removed from `parent`. This is also sent when the child is despawned.
+    ChildRemoved { child: Entity, parent: Entity },
+    /// `child` has been moved from `previous_parent` to `new_parent`.
+    ChildMoved {
+        child: Entity,
+        previous_parent: Entity,
+        new_parent: Entity,
+    },
+}
+
+/// Get the parent of an entity.
+pub fn parent_of(world: &World, entity: Entity) -> Option<Entity> {
+    world
+        .get_component::<Parent>(entity)
+        .ok()
+        .map(|parent| parent.0)
+}
+
+/// Get the children of an entity. Returns an empty [`Vec`] if it has none.
+pub fn children_of(world: &World, entity: Entity) -> Vec<Entity> {
+    world
+        .get_component::<Children>(entity)
+        .map(|children| children.0.clone())
+        .unwrap_or_default()
+}
+
+/// Get all descendants of an entity, depth-first in order of the children.
+pub fn descendants_of(world: &World, entity: Entity) -> Vec<Entity> {
+    let mut descendants = vec![];
+    let mut stack = children_of(world, entity);
+    stack.reverse();
+    while let Some(descendant) = stack.pop() {
+        descendants.push(descendant);
+        stack.extend(children_of(world, descendant).into_iter().rev());
+    }
+    descendants
+}
+
+/// Returns `true` if `ancestor` is a direct or indirect parent of `entity`.
+pub fn is_ancestor(world: &World, ancestor: Entity, entity: Entity) -> bool {
+    let mut current = parent_of(world, entity);
+    while let Some(parent) = current {
+        if parent == ancestor {
+            return true;
+        }
+        current = parent_of(world, parent);
+    }
+    false
+}
+
+/**
+Make `child` a child of `parent`. If `child` already has a parent, it is moved. Sends a [`HierarchyEvent`].
+
+# Errors
+
+Returns an error, when `parent` is `child` itself or one of its descendants, or when one of the entities doesn't exist.
+*/
+pub fn set_parent(world: &World, child: Entity, parent: Entity) -> Result<(), HierarchyError> {
+    if child == parent || is_ancestor(world, child, parent) {
+        return Err(HierarchyError::Cycle);
+    }
+    let previous_parent = parent_of(world, child);
+    if previous_parent == Some(parent) {
+        return Ok(());
+    }
+
+    if let Ok(mut children) = world.get_component_mut::<Children>(parent) {
+        children.0.push(child);
+    } else {
+        world
+            .assign_components((Children(vec![child]),), parent)
+            .map_err(|_| HierarchyError::EntityNotFound)?;
+        hook::inserted(world, parent, &[(TypeId::of::<Children>(), false)]);
+    }
+    if let Ok(mut current) = world.get_component_mut::<Parent>(child) {
+        current.0 = parent;
+    } else if world.assign_components((Parent(parent),), child).is_err() {
+        remove_child(world, parent, child);
+        return Err(HierarchyError::EntityNotFound);
+    } else {
+        hook::inserted(world, child, &[(TypeId::of::<Parent>(), false)]);
+    }
+
+    let event = match previous_parent {
+        Some(previous_parent) => {
+            remove_child(world, previous_parent, child);
+            HierarchyEvent::ChildMoved {
+                child,
+                previous_parent,
+                new_parent: parent,
+            }
+        }
+        None => HierarchyEvent::ChildAdded { child, parent },
+    };
+    world.send_event(event).unwrap();
+    Ok(())
+}
+
+/// Remove the parent of `child`, making it a root entity. Sends a [`HierarchyEvent`]. Does nothing if it has no parent.
+pub fn remove_parent(world: &World, child: Entity) {
+    let Some(parent) = parent_of(world, child) else {
+        return;
+    };
+    hook::removing(world, child, TypeId::of::<Parent>());
+    world.purge_component::<Parent>(child).unwrap();
+    remove_child(world, parent, child);
+    world
+        .send_event(HierarchyEvent::ChildRemoved { child, parent })
+        .unwrap();
+}
+
+/**
+Delete an entity and all of its descendants, dropping their component data. The entity is removed from the children of its parent.
+The [`HookKind::OnRemove`](crate::hook::HookKind::OnRemove) hooks of their components run before they are deleted.
+
+# Errors
+
+Returns an error, when the entity doesn't exist.
+*/
+pub fn despawn_recursive(world: &World, entity: Entity) -> Result<(), HierarchyError> {
+    remove_parent(world, entity);
+    for descendant in descendants_of(world, entity) {
+        hook::despawning(world, descendant);
+        if let Err(error) = world.purge_entity(descendant) {
+            warn!("failed to despawn descendant {}: {error}", descendant.id());
+        }
+    }
+    hook::despawning(world, entity);
+    world
+        .purge_entity(entity)
+        .map_err(|_| HierarchyError::EntityNotFound)
+}
+
+/// Remove `child` from the [`Children`] of `parent`. The component is removed, once there are no children left.
+fn remove_child(world: &World, parent: Entity, child: Entity) {
+    let empty = world
+        .get_component_mut::<Children>(parent)
+        .map(|mut children| {
+            children.0.retain(|entity| *entity != child);
+            children.0.is_empty()
+        })
+        .unwrap_or(false);
+    if empty {
+        hook::removing(world, parent, TypeId::of::<Children>());
+        world.purge_component::<Children>(parent).unwrap();
+    }
+}
+
+impl Commands<'_> {
+    /// Create an entity with the specified components as a child of `parent`.
+    pub fn spawn_child<C: ComponentSet + Send + 'static>(&self, parent: Entity, components: C) {
+        self.add(move |world| match world.create_entity(components) {
+            Ok(child) => {
+                hook::spawned(world, child, &C::type_ids());
+                if let Err(error) = set_parent(world, child, parent) {
+                    warn!("failed to add child to entity {}: {error}", parent.id());
+                }
+            }
+            Err(error) => warn!("failed to spawn child: {error}"),
+        });
+    }
+
+    /// Make `child` a child of `parent`. See [`set_parent`].
+    pub fn set_parent(&self, child: Entity, parent: Entity) {
+        self.add(move |world| {
+            if let Err(error) = set_parent(world, child, parent) {
+                warn!(
+                    "failed to add entity {} to entity {}: {error}",
+                    child.id(),
+                    parent.id()
+                );
+            }
+        });
+    }
+
+    /// Remove the parent of `child`. See [`remove_parent`].
+    pub fn remove_parent(&self, child: Entity) {
+        self.add(move |world| remove_parent(world, child));
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::Arc,
+};
+
+use magma_ecs::entities::Entity;
+
+use crate::World;
+
+/// A function run on a component lifecycle change of an entity.
+pub type ComponentHook = Arc<dyn Fn(&World, Entity) + Send + Sync>;
+
+/// The lifecycle changes of a component, which hooks can be registered for.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum HookKind {
+    /// The component has been added to an entity, which didn't have one before. Runs after the component has been added.
+    OnAdd,
+    /// The component has been added to an entity or replaced. Runs after [`HookKind::OnAdd`].
+    OnInsert,
+    /// The component is about to be removed from an entity or the entity is about to be despawned. Runs while the component can still be read.
+    OnRemove,
+}
+
+/// The hooks of one component type.
+struct Hooks {
+    /// returns `true` if the entity has the component
+    contains: fn(&World, Entity) -> bool,
+    on_add: Vec<ComponentHook>,
+    on_insert: Vec<ComponentHook>,
+    on_remove: Vec<ComponentHook>,
+}
+
+impl Hooks {
+    fn get(&self, kind: HookKind) -> &[ComponentHook] {
+        match kind {
+            HookKind::OnAdd => &self.on_add,
+            HookKind::OnInsert => &self.on_insert,
+            HookKind::OnRemove => &self.on_remove,
+        }
+    }
+}
+
+/// Resource containing the registered hooks per component type. It is added by the [`App`](crate::App).
+#[derive(Default)]
+pub(crate) struct ComponentHooks {
+    hooks: HashMap<TypeId, Hooks>,
+    /// the component types in the order their first hook has been registered
+    order: Vec<TypeId>,
+}
+
+impl ComponentHooks {
+    pub(crate) fn add<C: Any + Send + Sync>(&mut self, kind: HookKind, hook: ComponentHook) {
+        let type_id = TypeId::of::<C>();
+        let hooks = self.hooks.entry(type_id).or_insert_with(|| {
+            self.order.push(type_id);
+            Hooks {
+                contains: has_component::<C>,
+                on_add: vec![],
+                on_insert: vec![],
+                on_remove: vec![],
+            }
+        });
+        match kind {
+            HookKind::OnAdd => hooks.on_add.push(hook),
+            HookKind::OnInsert => hooks.on_insert.push(hook),
+            HookKind::OnRemove => hooks.on_remove.push(hook),
+        }
+    }
+}
+
+/// Returns `true` if `entity` has a component of type `C`. [`Commands`](crate::command::Commands) and the hierarchy functions purge removed components, so the data exists exactly when the entity has the component.
+fn has_component<C: Any + Send + Sync>(world: &World, entity: Entity) -> bool {
+    world.get_component::<C>(entity).is_ok()
+}
+
+/// Get the hooks of `kind` for the component type. The resource must not be locked while hooks run, so they can change the world.
+fn hooks_of(world: &World, type_id: TypeId, kind: HookKind) -> Vec<ComponentHook> {
+    let Ok(hooks) = world.get_resource::<ComponentHooks>() else {
+        return vec![];
+    };
+    hooks
+        .hooks
+        .get(&type_id)
+        .map(|hooks| hooks.get(kind).to_vec())
+        .unwrap_or_default()
+}
+
+/// Get the component types of `type_ids`, which have hooks, and whether `entity` has them.
+pub(crate) fn contained(world: &World, entity: Entity, type_ids: &[TypeId]) -> Vec<(TypeId, bool)> {
+    // worlds, which don't belong to an app, have no hooks
+    let Ok(hooks) = world.get_resource::<ComponentHooks>() else {
+        return vec![];
+    };
+    type_ids
+        .iter()
+        .filter_map(|type_id| {
+            hooks
+                .hooks
+                .get(type_id)
+                .map(|hooks| (*type_id, (hooks.contains)(world, entity)))
+        })
+        .collect()
+}
+
+/// Run the [`HookKind::OnAdd`] and [`HookKind::OnInsert`] hooks for components inserted into `entity`, which had the components in `before` before.
+pub(crate) fn inserted(world: &World, entity: Entity, before: &[(TypeId, bool)]) {
+    for (type_id, contained) in before {
+        if !contained {
+            run(world, entity, *type_id, HookKind::OnAdd);
+        }
+    }
+    for (type_id, _) in before {
+        run(world, entity, *type_id, HookKind::OnInsert);
+    }
+}
+
+/// Run the [`HookKind::OnAdd`] and [`HookKind::OnInsert`] hooks for the components of a new entity.
+pub(crate) fn spawned(world: &World, entity: Entity, type_ids: &[TypeId]) {
+    let before: Vec<(TypeId, bool)> = contained(world, entity, type_ids)
+        .into_iter()
+        .map(|(type_id, _)| (type_id, false))
+        .collect();
+    inserted(world, entity, &before);
+}
+
+/// Run the [`HookKind::OnRemove`] hooks of a component type, if `entity` has the component.
+pub(crate) fn removing(world: &World, entity: Entity, type_id: TypeId) {
+    if contained(world, entity, &[type_id])
+        .first()
+        .is_some_and(|(_, contained)| *contained)
+    {
+        run(world, entity, type_id, HookKind::OnRemove);
+    }
+}
+
+/// Run the [`HookKind::OnRemove`] hooks of all components of `entity`, which is about to be despawned. The component types are visited in the order their hooks have been registered.
+pub(crate) fn despawning(world: &World, entity: Entity) {
+    let type_ids: Vec<TypeId> = {
+        let Ok(hooks) = world.get_resource::<ComponentHooks>() else {
+            return;
+        };
+        hooks
+            .order
+            .iter()
+            .filter(|type_id| !hooks.hooks[*type_id].on_remove.is_empty())
+            .copied()
+            .collect()
+    };
+    for (type_id, contained) in contained(world, entity, &type_ids) {
+        if contained {
+            run(world, entity, type_id, HookKind::OnRemove);
+        }
+    }
+}
+
+fn run(world: &World, entity: Entity, type_id: TypeId, kind: HookKind) {
+    for hook in hooks_of(world, type_id, kind) {
+        hook(world, entity);
+    }
+}
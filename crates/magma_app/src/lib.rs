@@ -3,41 +3,98 @@ This crate provides basic functionality for creating and running an [`App`].
 A [`Module`] trait is also provided for implementing additional functionality.
 */
 use std::{
-    any::{Any, TypeId},
+    any::{Any, TypeId, type_name},
     collections::HashMap,
+    sync::Arc,
 };
 
+use change::{ChangeTicks, Tracked, update_ticks};
+use command::{CommandQueue, apply_commands};
+use event::{EventDispatchMode, Events, update_events};
+use hierarchy::{Children, HierarchyEvent, Parent};
+use hook::{ComponentHooks, HookKind};
 use magma_ecs::{
+    entities::Entity,
     error::EventError,
     rayon::iter::{IntoParallelRefIterator, ParallelIterator},
-    systems::{Systems, dispatcher::Dispatcher},
 };
-use module::Module;
+use module::{Dependencies, Module, ModuleGroup};
+use state::{AnyStateSchedules, NextState, State, StateSchedule, StateSchedules, States};
+use sub_app::{AppLabel, SubApp};
+use system::{ErrorHandler, Schedule, SetConfig, SystemConfig, SystemOutput, panic_on_error};
+use tracing::info_span;
 
+pub use exit::AppExit;
 pub use magma_ecs;
 pub use magma_ecs::{World, entities, rayon, resources, systems};
 pub use schedule::AppSchedule;
+pub use tracing;
 
 use crate::{
-    error::ScheduleError,
-    schedule::{PostUpdate, PreUpdate, Startup, Update},
+    error::{ModuleError, ScheduleError},
+    schedule::{
+        First, FixedUpdate, Last, PostStartup, PostUpdate, PreUpdate, Shutdown, Startup,
+        StateTransition, Update,
+    },
+    time::{FixedTime, Time},
 };
 
+/// Change ticks of components and resources and the [`Added`](change::Added) and [`Changed`](change::Changed) filters
+pub mod change;
+/// Deferred structural changes to the world
+pub mod command;
+/// Run conditions for systems and sets
+pub mod condition;
+/// Frame time, FPS, event and system diagnostics
+pub mod diagnostic;
 pub mod error;
+/// Double-buffered events with per-reader cursors
+pub mod event;
+mod exit;
+/// Parent-child relationships between entities
+pub mod hierarchy;
+/// Hooks run on component lifecycle changes
+pub mod hook;
 /// Support for adding [`Module`]s
 pub mod module;
 /// The [`AppSchedule`] trait and default schedules.
 pub mod schedule;
+/// A runner for apps without an event loop
+pub mod schedule_runner;
+/// States with schedules run on transitions
+pub mod state;
+/// Apps owned by other apps
+pub mod sub_app;
+/// Configuration of systems and sets
+pub mod system;
+/// Time related resources
+pub mod time;
 
 type SystemSlice = &'static [(fn(&World), &'static str, &'static [&'static str])];
+/// An event type and the function moving its pending events into its [`Events`] resource.
+type EventUpdate = (TypeId, fn(&World));
 
 /// The [`App`] struct holds all the apps data and defines the necessary functions and methods to operate on it.
 pub struct App {
     pub world: World,
-    runner: fn(App),
+    runner: fn(App) -> AppExit,
     modules: Vec<TypeId>,
-    systems: HashMap<TypeId, (Systems, Dispatcher)>,
-    event_systems: HashMap<TypeId, (Systems, Dispatcher)>,
+    pending_modules: Vec<TypeId>,
+    module_conflicts: Vec<(TypeId, &'static str)>,
+    finish_hooks: Vec<fn(&mut App)>,
+    cleanup_hooks: Vec<fn(&mut App)>,
+    startup_schedules: Vec<TypeId>,
+    main_schedules: Vec<TypeId>,
+    systems: HashMap<TypeId, Schedule>,
+    event_systems: HashMap<TypeId, Schedule>,
+    /// registered event types in order of registration
+    event_types: Vec<EventUpdate>,
+    event_order: Vec<(TypeId, TypeId)>,
+    event_dispatch_mode: EventDispatchMode,
+    error_handler: ErrorHandler,
+    /// schedules of the initialized states in order of initialization
+    states: Vec<Box<dyn AnyStateSchedules>>,
+    sub_apps: Vec<(TypeId, SubApp)>,
 }
 
 impl Default for App {
@@ -46,14 +103,51 @@ impl Default for App {
             world: Default::default(),
             runner: default_runner,
             modules: vec![],
+            pending_modules: vec![],
+            module_conflicts: vec![],
+            finish_hooks: vec![],
+            cleanup_hooks: vec![],
+            startup_schedules: vec![TypeId::of::<Startup>(), TypeId::of::<PostStartup>()],
+            main_schedules: vec![
+                TypeId::of::<First>(),
+                TypeId::of::<PreUpdate>(),
+                TypeId::of::<StateTransition>(),
+                TypeId::of::<FixedUpdate>(),
+                TypeId::of::<Update>(),
+                TypeId::of::<PostUpdate>(),
+                TypeId::of::<Last>(),
+            ],
             systems: Default::default(),
             event_systems: Default::default(),
+            event_types: vec![],
+            event_order: vec![],
+            event_dispatch_mode: EventDispatchMode::default(),
+            error_handler: panic_on_error,
+            states: vec![],
+            sub_apps: vec![],
         };
 
         app.register_schedule::<Startup>();
+        app.register_schedule::<PostStartup>();
+        app.register_schedule::<First>();
         app.register_schedule::<PreUpdate>();
+        app.register_schedule::<StateTransition>();
+        app.register_schedule::<FixedUpdate>();
         app.register_schedule::<Update>();
         app.register_schedule::<PostUpdate>();
+        app.register_schedule::<Last>();
+        app.register_schedule::<Shutdown>();
+
+        app.register_event::<AppExit>();
+        app.register_event::<HierarchyEvent>();
+        app.world.register_component::<Parent>();
+        app.world.register_component::<Children>();
+
+        app.world.add_resource(Time::default()).unwrap();
+        app.world.add_resource(FixedTime::default()).unwrap();
+        app.world.add_resource(CommandQueue::default()).unwrap();
+        app.world.add_resource(ComponentHooks::default()).unwrap();
+        app.world.add_resource(ChangeTicks::default()).unwrap();
 
         app
     }
@@ -68,6 +162,12 @@ impl App {
     /**
     Add a [`Module`] to the [`App`]. If it already exists, nothing happens.
 
+    The dependencies of the module are set up first. See [`Module::dependencies`].
+
+    # Panics
+
+    Panics if the module can't be added. Use [`App::try_add_module`] to handle the [`ModuleError`] instead.
+
     # Example
 
     ```
@@ -87,31 +187,369 @@ impl App {
     ```
     */
     pub fn add_module(&mut self, module: impl Module + 'static) {
-        let type_id = module.type_id();
-        if !self.modules.contains(&type_id) {
-            self.modules.push(type_id);
-            module.setup(self);
+        if let Err(error) = self.try_add_module(module) {
+            panic!("{error}");
+        }
+    }
+
+    /**
+    Add a [`Module`] to the [`App`]. If it already exists, nothing happens.
+
+    # Errors
+
+    Returns an error, when the module conflicts with an added module, a required module is missing or the dependencies form a cycle.
+    The module itself isn't set up in that case, but dependencies added before the failing check stay added.
+    */
+    pub fn try_add_module<M: Module + 'static>(&mut self, module: M) -> Result<(), ModuleError> {
+        let type_id = TypeId::of::<M>();
+        let name = type_name::<M>();
+        if self.modules.contains(&type_id) {
+            return Ok(());
+        }
+        if let Some(&(_, conflict)) = self.module_conflicts.iter().find(|(id, _)| *id == type_id) {
+            return Err(ModuleError::Conflict {
+                module: name,
+                conflict,
+            });
+        }
+
+        let mut dependencies = Dependencies::default();
+        module.dependencies(&mut dependencies);
+
+        self.pending_modules.push(type_id);
+        let result = self.resolve_dependencies(name, &mut dependencies);
+        self.pending_modules.pop();
+        result?;
+
+        self.module_conflicts.extend(
+            dependencies
+                .conflicts
+                .into_iter()
+                .map(|(conflict, _)| (conflict, name)),
+        );
+        self.modules.push(type_id);
+        module.setup(self);
+        self.finish_hooks.push(M::finish);
+        self.cleanup_hooks.push(M::cleanup);
+        Ok(())
+    }
+
+    /**
+    Call [`Module::finish`] for every added module, which hasn't been finished yet. This is done by [`App::run`].
+
+    Only call this manually, when updating the [`App`] without running it.
+    */
+    pub fn finish(&mut self) {
+        // modules added by finish hooks get finished as well
+        while !self.finish_hooks.is_empty() {
+            for finish in std::mem::take(&mut self.finish_hooks) {
+                finish(self);
+            }
+        }
+        for (_, sub_app) in &mut self.sub_apps {
+            sub_app.app.finish();
+        }
+    }
+
+    /**
+    Run the [`Shutdown`] schedule and call [`Module::cleanup`] for every added module in reverse order.
+
+    This should be called by the runner before it returns.
+    */
+    pub fn shutdown(&mut self) {
+        self.run_schedule::<Shutdown>().unwrap();
+        while let Some(cleanup) = self.cleanup_hooks.pop() {
+            cleanup(self);
+        }
+        for (_, sub_app) in &mut self.sub_apps {
+            sub_app.app.shutdown();
+        }
+    }
+
+    /// Insert a [`SubApp`] with the label `L`. If a sub-app with the same label exists, it is replaced.
+    pub fn insert_sub_app<L: AppLabel + 'static>(&mut self, sub_app: SubApp) {
+        self.remove_sub_app::<L>();
+        self.sub_apps.push((TypeId::of::<L>(), sub_app));
+    }
+
+    /// Remove the [`SubApp`] with the label `L` and return it.
+    pub fn remove_sub_app<L: AppLabel + 'static>(&mut self) -> Option<SubApp> {
+        let index = self
+            .sub_apps
+            .iter()
+            .position(|(label, _)| *label == TypeId::of::<L>())?;
+        Some(self.sub_apps.remove(index).1)
+    }
+
+    /// Get the [`SubApp`] with the label `L`.
+    pub fn sub_app<L: AppLabel + 'static>(&self) -> Option<&SubApp> {
+        self.sub_apps
+            .iter()
+            .find(|(label, _)| *label == TypeId::of::<L>())
+            .map(|(_, sub_app)| sub_app)
+    }
+
+    /// Get the [`SubApp`] with the label `L` mutably, e.g. to add modules to it.
+    pub fn sub_app_mut<L: AppLabel + 'static>(&mut self) -> Option<&mut SubApp> {
+        self.sub_apps
+            .iter_mut()
+            .find(|(label, _)| *label == TypeId::of::<L>())
+            .map(|(_, sub_app)| sub_app)
+    }
+
+    /**
+    Add a [`ModuleGroup`] to the [`App`]. The enabled members are added in order, see [`App::add_module`].
+
+    # Panics
+
+    Panics if a member can't be added. Use [`App::try_add_modules`] to handle the [`ModuleError`] instead.
+    */
+    pub fn add_modules(&mut self, group: impl ModuleGroup) {
+        if let Err(error) = self.try_add_modules(group) {
+            panic!("{error}");
+        }
+    }
+
+    /**
+    Add a [`ModuleGroup`] to the [`App`]. The enabled members are added in order, see [`App::try_add_module`].
+
+    # Errors
+
+    Returns the error of the first member, that can't be added. Members before it stay added.
+    */
+    pub fn try_add_modules(&mut self, group: impl ModuleGroup) -> Result<(), ModuleError> {
+        group.build().add_to(self)
+    }
+
+    fn resolve_dependencies(
+        &mut self,
+        module: &'static str,
+        dependencies: &mut Dependencies,
+    ) -> Result<(), ModuleError> {
+        // validate before setting up anything
+        if let Some(&(_, conflict)) = dependencies
+            .conflicts
+            .iter()
+            .find(|(type_id, _)| self.modules.contains(type_id))
+        {
+            return Err(ModuleError::Conflict { module, conflict });
+        }
+        if let Some(&(_, dependency, _)) = dependencies
+            .modules
+            .iter()
+            .find(|(type_id, _, _)| self.pending_modules.contains(type_id))
+        {
+            return Err(ModuleError::DependencyCycle { module, dependency });
+        }
+
+        for (_, _, add) in dependencies.modules.drain(..) {
+            add(self)?;
+        }
+
+        match dependencies
+            .required
+            .iter()
+            .find(|(type_id, _)| !self.modules.contains(type_id))
+        {
+            Some(&(_, dependency)) => Err(ModuleError::MissingDependency { module, dependency }),
+            None => Ok(()),
         }
     }
 
     /// Register an [`AppSchedule`].
     pub fn register_schedule<S: AppSchedule + 'static>(&mut self) {
-        self.systems.insert(TypeId::of::<S>(), Default::default());
+        self.systems
+            .insert(TypeId::of::<S>(), Schedule::new(type_name::<S>()));
     }
 
-    /// Run an [`AppSchedule`].
+    /**
+    Register an [`AppSchedule`] and insert it into the startup or main schedules right before `Target`.
+    If `S` is already part of them, it is moved.
+
+    # Errors
+
+    Returns an error, when `Target` isn't part of the startup or main schedules.
+
+    # Example
+
+    ```
+    use magma_app::{App, AppSchedule, schedule::Update};
+
+    struct LateUpdate;
+
+    impl AppSchedule for LateUpdate {}
+
+    let mut app = App::new();
+    app.insert_schedule_before::<Update, LateUpdate>().unwrap();
+    ```
+    */
+    pub fn insert_schedule_before<Target: AppSchedule + 'static, S: AppSchedule + 'static>(
+        &mut self,
+    ) -> Result<(), ScheduleError> {
+        self.insert_schedule(TypeId::of::<Target>(), TypeId::of::<S>(), 0)?;
+        self.systems
+            .entry(TypeId::of::<S>())
+            .or_insert_with(|| Schedule::new(type_name::<S>()));
+        Ok(())
+    }
+
+    /**
+    Register an [`AppSchedule`] and insert it into the startup or main schedules right after `Target`.
+    If `S` is already part of them, it is moved.
+
+    # Errors
+
+    Returns an error, when `Target` isn't part of the startup or main schedules.
+    */
+    pub fn insert_schedule_after<Target: AppSchedule + 'static, S: AppSchedule + 'static>(
+        &mut self,
+    ) -> Result<(), ScheduleError> {
+        self.insert_schedule(TypeId::of::<Target>(), TypeId::of::<S>(), 1)?;
+        self.systems
+            .entry(TypeId::of::<S>())
+            .or_insert_with(|| Schedule::new(type_name::<S>()));
+        Ok(())
+    }
+
+    fn insert_schedule(
+        &mut self,
+        target: TypeId,
+        schedule: TypeId,
+        offset: usize,
+    ) -> Result<(), ScheduleError> {
+        if !self.startup_schedules.contains(&target) && !self.main_schedules.contains(&target) {
+            return Err(ScheduleError::ScheduleNotOrdered);
+        }
+        if target == schedule {
+            return Ok(());
+        }
+        self.startup_schedules.retain(|id| *id != schedule);
+        self.main_schedules.retain(|id| *id != schedule);
+
+        let schedules = if self.startup_schedules.contains(&target) {
+            &mut self.startup_schedules
+        } else {
+            &mut self.main_schedules
+        };
+        let index = schedules.iter().position(|id| *id == target).unwrap();
+        schedules.insert(index + offset, schedule);
+        Ok(())
+    }
+
+    /// Run an [`AppSchedule`]. The [`Commands`](command::Commands) recorded by its systems are applied afterwards.
     pub fn run_schedule<S: AppSchedule + 'static>(&self) -> Result<(), ScheduleError> {
+        self.dispatch_schedule(&TypeId::of::<S>())
+    }
+
+    fn dispatch_schedule(&self, schedule: &TypeId) -> Result<(), ScheduleError> {
         self.systems
-            .get(&TypeId::of::<S>())
+            .get(schedule)
             .ok_or(ScheduleError::ScheduleNotRegistered)?
-            .1
-            .dispatch(&self.world);
+            .run(&self.world, self.error_handler)?;
+        self.apply_commands();
         Ok(())
     }
 
+    /// Apply the [`Commands`](command::Commands) recorded since the last sync point and record the changes of tracked components and resources.
+    /// This is done by the [`App`] after every schedule.
+    pub fn apply_commands(&self) {
+        apply_commands(&self.world);
+        update_ticks(&self.world);
+    }
+
+    /**
+    Build the execution graphs of all schedules and event systems, which haven't been built yet. This is done by [`App::run`].
+
+    # Errors
+
+    Returns an error, when systems have duplicate names, depend on unknown systems or sets or form a dependency cycle.
+    */
+    pub fn validate_schedules(&self) -> Result<(), ScheduleError> {
+        self.systems
+            .values()
+            .chain(self.event_systems.values())
+            .try_for_each(Schedule::validate)?;
+        self.states.iter().try_for_each(|state| state.validate())?;
+        self.sub_apps
+            .iter()
+            .try_for_each(|(_, sub_app)| sub_app.app.validate_schedules())
+    }
+
+    /**
+    Initialize a [`State`] of type `S` with the initial `state`. This adds the [`State`] and [`NextState`] resources.
+    If the state is already initialized, nothing happens.
+
+    [`StateSchedule::OnEnter`] of the initial state runs at the end of [`App::startup`].
+    */
+    pub fn init_state<S: States>(&mut self, state: S) {
+        if self.state_schedules::<S>().is_some() {
+            return;
+        }
+        self.world.add_resource(State(state)).unwrap();
+        self.world.add_resource(NextState::<S>::default()).unwrap();
+        self.states.push(Box::new(StateSchedules::<S>::default()));
+    }
+
+    /**
+    Add systems to a [`StateSchedule`]. See [`App::add_systems`].
+
+    # Errors
+
+    Returns an error, when the state isn't initialized.
+    */
+    pub fn add_state_systems<S: States>(
+        &mut self,
+        schedule: StateSchedule<S>,
+        systems: SystemSlice,
+    ) -> Result<(), ScheduleError> {
+        let schedule = self
+            .state_schedules::<S>()
+            .ok_or(ScheduleError::ScheduleNotRegistered)?
+            .schedule_mut(schedule);
+        for system in systems {
+            schedule.add_system(system_config(system));
+        }
+        Ok(())
+    }
+
+    /**
+    Add a system configured with a [`SystemConfig`] to a [`StateSchedule`].
+
+    # Errors
+
+    Returns an error, when the state isn't initialized.
+    */
+    pub fn add_state_system<S: States>(
+        &mut self,
+        schedule: StateSchedule<S>,
+        system: SystemConfig,
+    ) -> Result<(), ScheduleError> {
+        self.state_schedules::<S>()
+            .ok_or(ScheduleError::ScheduleNotRegistered)?
+            .schedule_mut(schedule)
+            .add_system(system);
+        Ok(())
+    }
+
+    fn state_schedules<S: States>(&mut self) -> Option<&mut StateSchedules<S>> {
+        self.states
+            .iter_mut()
+            .find_map(|state| state.as_any_mut().downcast_mut::<StateSchedules<S>>())
+    }
+
+    /// Apply the pending transitions of all states. This is done in the [`StateTransition`] schedule.
+    fn apply_state_transitions(&self) {
+        for state in &self.states {
+            state.apply_transition(self);
+        }
+    }
+
     /**
     Add systems to the [`App`]'s [`World`]. Systems must take an immutable reference to [`World`].
 
+    Each system is specified by a function, a unique name and the names of the systems or sets it runs after.
+    Use [`App::add_system`] for closures, stateful systems and more configuration options.
+
     # Errors
 
     Returns an error, when the schedule isn't registered.
@@ -138,18 +576,326 @@ impl App {
             .systems
             .get_mut(&TypeId::of::<S>())
             .ok_or(ScheduleError::ScheduleNotRegistered)?;
-        for (run, name, deps) in systems {
-            schedule.0.add(*run, name, deps);
+        for system in systems {
+            schedule.add_system(system_config(system));
         }
+        Ok(())
+    }
 
-        schedule.1 = schedule.0.to_owned().build_dispatcher();
+    /**
+    Add a system configured with a [`SystemConfig`] to an [`AppSchedule`].
+
+    Ordering and conditions are validated, when the schedule is built. See [`App::validate_schedules`].
+
+    # Errors
+
+    Returns an error, when the schedule isn't registered.
+    */
+    pub fn add_system<S: AppSchedule + 'static>(
+        &mut self,
+        system: SystemConfig,
+    ) -> Result<(), ScheduleError> {
+        self.systems
+            .get_mut(&TypeId::of::<S>())
+            .ok_or(ScheduleError::ScheduleNotRegistered)?
+            .add_system(system);
         Ok(())
     }
 
+    /**
+    Remove a system from an [`AppSchedule`]. Returns the removed [`SystemConfig`], so it can be added again later.
+
+    Systems ordered relative to the removed system must not depend on it anymore, otherwise the schedule becomes invalid.
+
+    # Errors
+
+    Returns an error, when the schedule isn't registered or doesn't contain a system with the specified name.
+    */
+    pub fn remove_system<S: AppSchedule + 'static>(
+        &mut self,
+        name: &str,
+    ) -> Result<SystemConfig, ScheduleError> {
+        self.systems
+            .get_mut(&TypeId::of::<S>())
+            .ok_or(ScheduleError::ScheduleNotRegistered)?
+            .remove_system(name)
+    }
+
+    /**
+    Replace the function of a system in an [`AppSchedule`]. The ordering and conditions of the system are kept.
+
+    # Errors
+
+    Returns an error, when the schedule isn't registered or doesn't contain a system with the specified name.
+
+    # Example
+
+    ```
+    use magma_app::{App, World, schedule::Update};
+
+    let mut app = App::new();
+    app.add_systems::<Update>(&[(walk, "movement", &[])]).unwrap();
+    app.replace_system::<Update, _>("movement", fly).unwrap();
+
+    fn walk(_world: &World) {}
+
+    fn fly(_world: &World) {}
+    ```
+    */
+    pub fn replace_system<S: AppSchedule + 'static, O: SystemOutput>(
+        &mut self,
+        name: &str,
+        run: impl Fn(&World) -> O + Send + Sync + 'static,
+    ) -> Result<(), ScheduleError> {
+        self.systems
+            .get_mut(&TypeId::of::<S>())
+            .ok_or(ScheduleError::ScheduleNotRegistered)?
+            .replace_system(name, Box::new(move |world| run(world).into_result()))
+    }
+
+    /**
+    Enable or disable a system in an [`AppSchedule`]. Disabled systems are skipped, but keep their place in the ordering.
+
+    # Errors
+
+    Returns an error, when the schedule isn't registered or doesn't contain a system with the specified name.
+    */
+    pub fn set_system_enabled<S: AppSchedule + 'static>(
+        &mut self,
+        name: &str,
+        enabled: bool,
+    ) -> Result<(), ScheduleError> {
+        self.systems
+            .get_mut(&TypeId::of::<S>())
+            .ok_or(ScheduleError::ScheduleNotRegistered)?
+            .set_system_enabled(name, enabled)
+    }
+
+    /**
+    Returns `true` if the system in the [`AppSchedule`] is enabled.
+
+    # Errors
+
+    Returns an error, when the schedule isn't registered or doesn't contain a system with the specified name.
+    */
+    pub fn system_enabled<S: AppSchedule + 'static>(
+        &self,
+        name: &str,
+    ) -> Result<bool, ScheduleError> {
+        self.systems
+            .get(&TypeId::of::<S>())
+            .ok_or(ScheduleError::ScheduleNotRegistered)?
+            .system_enabled(name)
+    }
+
+    /**
+    Configure the ordering and conditions of a set in an [`AppSchedule`]. Configuring a set again replaces its previous configuration.
+
+    # Errors
+
+    Returns an error, when the schedule isn't registered.
+    */
+    pub fn configure_set<S: AppSchedule + 'static>(
+        &mut self,
+        set: SetConfig,
+    ) -> Result<(), ScheduleError> {
+        self.systems
+            .get_mut(&TypeId::of::<S>())
+            .ok_or(ScheduleError::ScheduleNotRegistered)?
+            .configure_set(set);
+        Ok(())
+    }
+
+    /// Register an event type. Systems can be added to run, when events of this type have been sent.
+    /// The sent events can also be read from the [`Events`] resource of the type. If it is already registered, nothing happens.
     pub fn register_event<E: Any + Send + Sync + Clone>(&mut self) {
+        if self.event_systems.contains_key(&TypeId::of::<E>()) {
+            return;
+        }
         self.world.register_event::<E>();
+        self.world.add_resource(Events::<E>::default()).unwrap();
         self.event_systems
-            .insert(TypeId::of::<E>(), (Systems::new(), Dispatcher::default()));
+            .insert(TypeId::of::<E>(), Schedule::new(type_name::<E>()));
+        self.event_types
+            .push((TypeId::of::<E>(), update_events::<E>));
+    }
+
+    /**
+    Register a hook, which runs when a component of type `C` is added to, inserted into or removed from an entity. See [`HookKind`].
+
+    Hooks run for changes made through [`Commands`](command::Commands) and the [`hierarchy`] functions, including recursive despawning.
+    Changes made directly through the [`World`] don't run hooks. Hooks run in order of registration and can change the world.
+
+    # Example
+
+    ```
+    use magma_app::{App, World, command::Commands, entities::Entity, hook::HookKind, schedule::Update};
+
+    struct Enemy;
+
+    let mut app = App::new();
+    app.world.register_component::<Enemy>();
+    app.world.add_resource(0_u32).unwrap();
+    app.add_component_hook::<Enemy>(HookKind::OnAdd, count_enemies);
+    app.add_systems::<Update>(&[(spawn_enemy, "spawn_enemy", &[])]).unwrap();
+    app.update();
+
+    assert_eq!(*app.world.get_resource::<u32>().unwrap(), 1);
+
+    fn count_enemies(world: &World, _entity: Entity) {
+        *world.get_resource_mut::<u32>().unwrap() += 1;
+    }
+
+    fn spawn_enemy(world: &World) {
+        Commands::new(world).spawn((Enemy,));
+    }
+    ```
+    */
+    pub fn add_component_hook<C: Any + Send + Sync>(
+        &mut self,
+        kind: HookKind,
+        hook: impl Fn(&World, Entity) + Send + Sync + 'static,
+    ) {
+        self.world
+            .get_resource_mut::<ComponentHooks>()
+            .unwrap()
+            .add::<C>(kind, Arc::new(hook));
+    }
+
+    /**
+    Track the changes of the component `C`, so they can be found with [`Added`](change::Added) and [`Changed`](change::Changed).
+    The component has to be registered. If it is already tracked, nothing happens.
+
+    Changes are recorded using the added, changed and removed queries of the [`World`], which only report a change once.
+    Don't use these queries for tracked components.
+    */
+    pub fn track_changes<C: Any + Send + Sync>(&mut self) {
+        self.world
+            .get_resource_mut::<ChangeTicks>()
+            .unwrap()
+            .track::<C>();
+    }
+
+    /**
+    Add a resource wrapped in [`Tracked`] and record its changes. See [`Tracked::is_changed_since`].
+
+    # Panics
+
+    Panics if a [`Tracked<R>`] resource already exists.
+    */
+    pub fn add_tracked_resource<R: Any + Send + Sync>(&mut self, resource: R) {
+        self.world.add_resource(Tracked::new(resource)).unwrap();
+        self.world
+            .get_resource_mut::<ChangeTicks>()
+            .unwrap()
+            .track_resource::<R>();
+    }
+
+    /**
+    Declare that the event systems of `A` run before the event systems of `B`, when events of both types are pending.
+    The ordering is transitive, even if events of intermediate types aren't pending.
+
+    # Errors
+
+    Returns an error, when the ordering would form a cycle.
+
+    # Example
+
+    ```
+    use magma_app::App;
+
+    #[derive(Clone)]
+    struct Resized;
+    #[derive(Clone)]
+    struct CursorMoved;
+
+    let mut app = App::new();
+    app.register_event::<Resized>();
+    app.register_event::<CursorMoved>();
+    app.order_event_systems::<Resized, CursorMoved>().unwrap();
+    ```
+    */
+    pub fn order_event_systems<A: Any + Send + Sync + Clone, B: Any + Send + Sync + Clone>(
+        &mut self,
+    ) -> Result<(), ScheduleError> {
+        let (before, after) = (TypeId::of::<A>(), TypeId::of::<B>());
+        if before == after || self.event_precedes(after, before) {
+            return Err(ScheduleError::EventOrderCycle {
+                event: type_name::<A>(),
+            });
+        }
+        self.event_order.push((before, after));
+        Ok(())
+    }
+
+    /**
+    Set the [`ErrorHandler`], which is called when a system returns an error. The default handler [`panic_on_error`] panics.
+
+    # Example
+
+    ```
+    use magma_app::{App, World, error::SystemError, system::ErrorAction};
+
+    let mut app = App::new();
+    app.set_error_handler(count_errors);
+
+    fn count_errors(world: &World, error: &SystemError) -> ErrorAction {
+        eprintln!("{error}");
+        if let Ok(mut errors) = world.get_resource_mut::<u32>() {
+            *errors += 1;
+        }
+        ErrorAction::Continue
+    }
+    ```
+    */
+    pub fn set_error_handler(&mut self, handler: ErrorHandler) {
+        self.error_handler = handler;
+    }
+
+    /// Set how the systems of different event types are run. See [`EventDispatchMode`].
+    pub fn set_event_dispatch_mode(&mut self, mode: EventDispatchMode) {
+        self.event_dispatch_mode = mode;
+    }
+
+    /// Returns `true` if the systems of `before` have to run before the systems of `after`.
+    fn event_precedes(&self, before: TypeId, after: TypeId) -> bool {
+        let mut stack = vec![before];
+        let mut visited = vec![];
+        while let Some(current) = stack.pop() {
+            for &(_, next) in self.event_order.iter().filter(|(from, _)| *from == current) {
+                if next == after {
+                    return true;
+                }
+                if !visited.contains(&next) {
+                    visited.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+        false
+    }
+
+    /// Group the pending event types into stages, which respect the declared ordering.
+    fn event_stages(&self, pending: &[TypeId]) -> Vec<Vec<TypeId>> {
+        // order of registration makes sequential dispatch deterministic
+        let mut remaining: Vec<TypeId> = self
+            .event_types
+            .iter()
+            .map(|(type_id, _)| *type_id)
+            .filter(|type_id| pending.contains(type_id))
+            .collect();
+        let mut stages = vec![];
+        while !remaining.is_empty() {
+            let (stage, rest): (Vec<TypeId>, Vec<TypeId>) =
+                remaining.iter().copied().partition(|&type_id| {
+                    !remaining
+                        .iter()
+                        .any(|&other| self.event_precedes(other, type_id))
+                });
+            stages.push(stage);
+            remaining = rest;
+        }
+        stages
     }
 
     pub fn add_event_systems<E: Any + Send + Sync + Clone>(
@@ -160,45 +906,172 @@ impl App {
             .event_systems
             .get_mut(&TypeId::of::<E>())
             .ok_or(EventError::EventNotRegistered)?;
-        for (run, name, deps) in systems {
-            event_systems.0.add(*run, name, deps);
+        for system in systems {
+            event_systems.add_system(system_config(system));
         }
-        event_systems.1 = event_systems.0.to_owned().build_dispatcher();
         Ok(())
     }
 
-    /// Set the runner of the [`App`]
-    pub fn set_runner(&mut self, runner: fn(App)) {
+    /**
+    Add a system configured with a [`SystemConfig`], which runs when events of type `E` have been sent.
+
+    # Errors
+
+    Returns an error, when the event isn't registered.
+    */
+    pub fn add_event_system<E: Any + Send + Sync + Clone>(
+        &mut self,
+        system: SystemConfig,
+    ) -> Result<(), EventError> {
+        self.event_systems
+            .get_mut(&TypeId::of::<E>())
+            .ok_or(EventError::EventNotRegistered)?
+            .add_system(system);
+        Ok(())
+    }
+
+    /**
+    Set the runner of the [`App`]. The runner should return once an [`AppExit`] event has been received.
+
+    The runner is responsible for calling [`App::startup`] once, [`App::update`] in a loop and [`App::shutdown`] before returning.
+    */
+    pub fn set_runner(&mut self, runner: fn(App) -> AppExit) {
         self.runner = runner;
     }
 
-    /// Process pending events.
-    pub fn process_events(&self) {
-        let events = self.world.get_pending_events();
-        // dispatch systems for events
-        events.par_iter().for_each(|type_id| {
+    /**
+    Process pending events. This runs the event systems of every pending event type and moves the events into their [`Events`] resources.
+
+    Returns the [`AppExit`] event, if one has been sent.
+    */
+    pub fn process_events(&self) -> Option<AppExit> {
+        let _span = info_span!("process_events").entered();
+        let pending = self.world.get_pending_events();
+        let dispatch = |type_id: &TypeId| {
             self.event_systems
                 .get(type_id)
                 .unwrap()
-                .1
-                .dispatch(&self.world);
+                .run(&self.world, self.error_handler)
+                .unwrap();
+        };
+        // dispatch systems for events
+        info_span!("dispatch_event_systems", pending = pending.len()).in_scope(|| {
+            for stage in self.event_stages(&pending) {
+                match self.event_dispatch_mode {
+                    EventDispatchMode::Parallel => stage.par_iter().for_each(dispatch),
+                    EventDispatchMode::Sequential => stage.iter().for_each(dispatch),
+                }
+            }
+        });
+        self.apply_commands();
+        info_span!("update_events").in_scope(|| {
+            self.event_types
+                .par_iter()
+                .for_each(|(_, update)| update(&self.world));
         });
 
+        let exit = {
+            let exits = self.world.poll_events::<AppExit>().unwrap();
+            exits
+                .iter()
+                .find(|exit| exit.is_error())
+                .or(exits.first())
+                .copied()
+        };
+
         self.world.clear_events();
+        exit
+    }
+
+    /// Run the startup schedules. By default these are [`Startup`] and [`PostStartup`]. Afterwards the initial states are entered.
+    pub fn startup(&self) {
+        for schedule in &self.startup_schedules {
+            self.dispatch_schedule(schedule).unwrap();
+        }
+        self.apply_state_transitions();
+        for (_, sub_app) in &self.sub_apps {
+            sub_app.app.startup();
+        }
+    }
+
+    /**
+    Run a single frame. This updates the [`Time`] resource, runs the main schedules and processes pending events afterwards.
+    If a [`Diagnostics`](diagnostic::Diagnostics) resource exists, the diagnostics of the frame are recorded after processing the events.
+    By default the main schedules are [`First`], [`PreUpdate`], [`StateTransition`], [`FixedUpdate`], [`Update`], [`PostUpdate`] and [`Last`].
+
+    [`FixedUpdate`] runs as many times as there are steps accumulated in the [`FixedTime`] resource.
+    After the events have been processed, the [`SubApp`]s are updated in order of insertion.
+
+    Returns the [`AppExit`] event, if one has been sent during the frame. Errors take precedence.
+    */
+    pub fn update(&self) -> Option<AppExit> {
+        let _span = info_span!("frame").entered();
+        self.world.get_resource_mut::<Time>().unwrap().update();
+        for schedule in &self.main_schedules {
+            if *schedule == TypeId::of::<FixedUpdate>() {
+                self.run_fixed_update();
+            } else if *schedule == TypeId::of::<StateTransition>() {
+                self.apply_state_transitions();
+                self.dispatch_schedule(schedule).unwrap();
+            } else {
+                self.dispatch_schedule(schedule).unwrap();
+            }
+        }
+        let exit = self.process_events();
+        diagnostic::record_frame(&self.world);
+
+        let sub_app_exit = self
+            .sub_apps
+            .iter()
+            .filter_map(|(_, sub_app)| sub_app.update(&self.world))
+            .max();
+        exit.max(sub_app_exit)
     }
 
-    /// Run the Application
-    pub fn run(self) {
-        (self.runner)(self);
+    fn run_fixed_update(&self) {
+        let delta = self.world.get_resource::<Time>().unwrap().delta();
+        self.world
+            .get_resource_mut::<FixedTime>()
+            .unwrap()
+            .accumulate(delta);
+        // the resource must not be locked while the schedule runs
+        while self.world.get_resource_mut::<FixedTime>().unwrap().expend() {
+            self.run_schedule::<FixedUpdate>().unwrap();
+        }
+    }
+
+    /**
+    Run the Application. Returns once the [`App`] has exited.
+
+    # Panics
+
+    Panics if a schedule is invalid. See [`App::validate_schedules`].
+    */
+    pub fn run(mut self) -> AppExit {
+        self.finish();
+        if let Err(error) = self.validate_schedules() {
+            panic!("{error}");
+        }
+        (self.runner)(self)
     }
 }
 
-fn default_runner(app: App) {
-    app.run_schedule::<Startup>().unwrap();
+fn system_config(
+    &(run, name, dependencies): &(fn(&World), &'static str, &'static [&'static str]),
+) -> SystemConfig {
+    dependencies
+        .iter()
+        .fold(SystemConfig::new(name, run), |system, dependency| {
+            system.after(dependency)
+        })
+}
+
+fn default_runner(mut app: App) -> AppExit {
+    app.startup();
     loop {
-        app.run_schedule::<PreUpdate>().unwrap();
-        app.run_schedule::<Update>().unwrap();
-        app.run_schedule::<PostUpdate>().unwrap();
-        app.process_events();
+        if let Some(exit) = app.update() {
+            app.shutdown();
+            return exit;
+        }
     }
 }
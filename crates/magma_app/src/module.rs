@@ -1,6 +1,261 @@
-use crate::App;
+use std::any::{TypeId, type_name};
 
-/// Must be implemented on your own modules for them to work
+use crate::{App, error::ModuleError};
+
+/**
+Must be implemented on your own modules for them to work
+
+# Example
+
+```
+use magma_app::{App, module::{Dependencies, Module}};
+
+#[derive(Default)]
+struct PhysicsModule;
+
+impl Module for PhysicsModule {
+    fn setup(self, app: &mut App) {}
+}
+
+struct GameModule;
+
+impl Module for GameModule {
+    fn setup(self, app: &mut App) {
+        // PhysicsModule is guaranteed to be set up at this point
+    }
+
+    fn dependencies(&self, dependencies: &mut Dependencies) {
+        dependencies.add::<PhysicsModule>();
+    }
+}
+
+let mut app = App::new();
+app.add_module(GameModule);
+```
+*/
 pub trait Module {
     fn setup(self, app: &mut App);
+
+    /// Called once after all modules have been added, right before the [`App`] runs.
+    fn finish(_app: &mut App) {}
+
+    /// Called once when the [`App`] shuts down, after the [`Shutdown`](crate::schedule::Shutdown) schedule.
+    /// Modules are cleaned up in reverse order of their setup.
+    fn cleanup(_app: &mut App) {}
+
+    /// Declare which modules this module depends on or conflicts with. Dependencies are set up before this module.
+    fn dependencies(&self, _dependencies: &mut Dependencies) {}
+}
+
+type AddModule = Box<dyn FnOnce(&mut App) -> Result<(), ModuleError>>;
+
+/// The dependencies and conflicts of a [`Module`]. See [`Module::dependencies`].
+#[derive(Default)]
+pub struct Dependencies {
+    pub(crate) modules: Vec<(TypeId, &'static str, AddModule)>,
+    pub(crate) required: Vec<(TypeId, &'static str)>,
+    pub(crate) conflicts: Vec<(TypeId, &'static str)>,
+}
+
+impl Dependencies {
+    /// Depend on `M`. If it hasn't been added to the [`App`] yet, its default configuration is added first.
+    pub fn add<M: Module + Default + 'static>(&mut self) -> &mut Self {
+        self.modules.push((
+            TypeId::of::<M>(),
+            type_name::<M>(),
+            Box::new(|app: &mut App| app.try_add_module(M::default())),
+        ));
+        self
+    }
+
+    /// Require `M` to be added to the [`App`] first. Unlike [`Dependencies::add`], `M` is not added automatically.
+    pub fn require<M: Module + 'static>(&mut self) -> &mut Self {
+        self.required.push((TypeId::of::<M>(), type_name::<M>()));
+        self
+    }
+
+    /// Declare that `M` can't be added to the same [`App`] as this module.
+    pub fn conflict<M: Module + 'static>(&mut self) -> &mut Self {
+        self.conflicts.push((TypeId::of::<M>(), type_name::<M>()));
+        self
+    }
+}
+
+/**
+A group of [`Module`]s, which can be added to an [`App`] at once with [`App::add_modules`].
+
+The members of a group can be disabled, replaced with a configured instance or extended using the [`ModuleGroupBuilder`].
+
+# Example
+
+```
+use magma_app::{App, module::{Module, ModuleGroup, ModuleGroupBuilder}};
+
+struct AModule;
+
+impl Module for AModule {
+    fn setup(self, app: &mut App) {}
+}
+
+struct BModule(u32);
+
+impl Module for BModule {
+    fn setup(self, app: &mut App) {}
+}
+
+struct ExampleModules;
+
+impl ModuleGroup for ExampleModules {
+    fn build(self) -> ModuleGroupBuilder {
+        ModuleGroupBuilder::new().add_module(AModule).add_module(BModule(1))
+    }
+}
+
+let mut app = App::new();
+app.add_modules(
+    ExampleModules
+        .build()
+        .disable::<AModule>()
+        .set(BModule(2)),
+);
+```
+*/
+pub trait ModuleGroup {
+    fn build(self) -> ModuleGroupBuilder;
+}
+
+struct ModuleGroupEntry {
+    type_id: TypeId,
+    enabled: bool,
+    add: AddModule,
+}
+
+/// Builder for the members of a [`ModuleGroup`]. Modules are added to the [`App`] in the order of the group.
+#[derive(Default)]
+pub struct ModuleGroupBuilder {
+    modules: Vec<ModuleGroupEntry>,
+}
+
+impl ModuleGroup for ModuleGroupBuilder {
+    fn build(self) -> ModuleGroupBuilder {
+        self
+    }
+}
+
+impl ModuleGroupBuilder {
+    /// Create an empty [`ModuleGroupBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a module to the end of the group. If the group already contains a module of the same type, it is removed first.
+    pub fn add_module<M: Module + 'static>(mut self, module: M) -> Self {
+        self.remove_type(TypeId::of::<M>());
+        self.modules.push(Self::entry(module));
+        self
+    }
+
+    /**
+    Add a module to the group right before `Target`. If the group already contains a module of the same type, it is removed first.
+
+    # Panics
+
+    Panics if `Target` is not part of the group.
+    */
+    pub fn add_before<Target: Module + 'static>(mut self, module: impl Module + 'static) -> Self {
+        let entry = Self::entry(module);
+        self.remove_type(entry.type_id);
+        let index = self.index_of::<Target>();
+        self.modules.insert(index, entry);
+        self
+    }
+
+    /**
+    Add a module to the group right after `Target`. If the group already contains a module of the same type, it is removed first.
+
+    # Panics
+
+    Panics if `Target` is not part of the group.
+    */
+    pub fn add_after<Target: Module + 'static>(mut self, module: impl Module + 'static) -> Self {
+        let entry = Self::entry(module);
+        self.remove_type(entry.type_id);
+        let index = self.index_of::<Target>();
+        self.modules.insert(index + 1, entry);
+        self
+    }
+
+    /**
+    Replace a member of the group with the specified instance, e.g. to configure it.
+
+    # Panics
+
+    Panics if a module of the same type is not part of the group.
+    */
+    pub fn set<M: Module + 'static>(mut self, module: M) -> Self {
+        let index = self.index_of::<M>();
+        let enabled = self.modules[index].enabled;
+        self.modules[index] = Self::entry(module);
+        self.modules[index].enabled = enabled;
+        self
+    }
+
+    /**
+    Disable a member of the group. It will not be added to the [`App`], unless another module depends on it.
+
+    # Panics
+
+    Panics if `M` is not part of the group.
+    */
+    pub fn disable<M: Module + 'static>(mut self) -> Self {
+        let index = self.index_of::<M>();
+        self.modules[index].enabled = false;
+        self
+    }
+
+    /**
+    Enable a previously disabled member of the group.
+
+    # Panics
+
+    Panics if `M` is not part of the group.
+    */
+    pub fn enable<M: Module + 'static>(mut self) -> Self {
+        let index = self.index_of::<M>();
+        self.modules[index].enabled = true;
+        self
+    }
+
+    /// Returns `true` if `M` is part of the group and enabled.
+    pub fn enabled<M: Module + 'static>(&self) -> bool {
+        self.modules
+            .iter()
+            .any(|entry| entry.type_id == TypeId::of::<M>() && entry.enabled)
+    }
+
+    pub(crate) fn add_to(self, app: &mut App) -> Result<(), ModuleError> {
+        self.modules
+            .into_iter()
+            .filter(|entry| entry.enabled)
+            .try_for_each(|entry| (entry.add)(app))
+    }
+
+    fn entry<M: Module + 'static>(module: M) -> ModuleGroupEntry {
+        ModuleGroupEntry {
+            type_id: TypeId::of::<M>(),
+            enabled: true,
+            add: Box::new(|app: &mut App| app.try_add_module(module)),
+        }
+    }
+
+    fn index_of<M: Module + 'static>(&self) -> usize {
+        self.modules
+            .iter()
+            .position(|entry| entry.type_id == TypeId::of::<M>())
+            .unwrap_or_else(|| panic!("module `{}` is not part of the group", type_name::<M>()))
+    }
+
+    fn remove_type(&mut self, type_id: TypeId) {
+        self.modules.retain(|entry| entry.type_id != type_id);
+    }
 }
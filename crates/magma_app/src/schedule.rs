@@ -4,10 +4,30 @@ pub struct Startup;
 
 impl AppSchedule for Startup {}
 
+/// Runs once after [`Startup`].
+pub struct PostStartup;
+
+impl AppSchedule for PostStartup {}
+
+/// Runs at the very beginning of every frame.
+pub struct First;
+
+impl AppSchedule for First {}
+
 pub struct PreUpdate;
 
 impl AppSchedule for PreUpdate {}
 
+/// Applies pending [`NextState`](crate::state::NextState) transitions and runs the state schedules. Runs after [`PreUpdate`].
+pub struct StateTransition;
+
+impl AppSchedule for StateTransition {}
+
+/// Runs zero or more times per frame at a fixed rate. See [`FixedTime`](crate::time::FixedTime).
+pub struct FixedUpdate;
+
+impl AppSchedule for FixedUpdate {}
+
 pub struct Update;
 
 impl AppSchedule for Update {}
@@ -15,3 +35,13 @@ impl AppSchedule for Update {}
 pub struct PostUpdate;
 
 impl AppSchedule for PostUpdate {}
+
+/// Runs at the very end of every frame, before events are processed.
+pub struct Last;
+
+impl AppSchedule for Last {}
+
+/// Runs once after the [`App`](crate::App) has received an [`AppExit`](crate::AppExit) event.
+pub struct Shutdown;
+
+impl AppSchedule for Shutdown {}
@@ -0,0 +1,120 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{App, AppExit, module::Module};
+
+/// How the runner of the [`ScheduleRunnerModule`] updates the [`App`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RunMode {
+    /// Update the [`App`] once.
+    Once,
+    /// Update the [`App`] the specified amount of times.
+    Frames(u64),
+    /// Update the [`App`] in a loop. If `wait` is set, every frame takes at least this long.
+    Loop { wait: Option<Duration> },
+}
+
+impl Default for RunMode {
+    fn default() -> Self {
+        Self::Loop { wait: None }
+    }
+}
+
+/// Resource containing the [`RunMode`] of the [`ScheduleRunnerModule`]. It can be changed before the [`App`] runs.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct ScheduleRunnerSettings {
+    pub run_mode: RunMode,
+}
+
+/**
+Module setting a runner, which updates the [`App`] without an event loop. This is useful for headless apps and tests.
+
+The runner returns the first [`AppExit`] event sent. If the [`RunMode`] ends without one, it returns [`AppExit::Success`].
+
+To step the [`App`] manually, don't run it. Instead call [`App::finish`] and [`App::startup`] once and [`App::update`] for every frame.
+
+# Example
+
+```
+use magma_app::{App, AppExit, schedule_runner::ScheduleRunnerModule};
+
+let mut app = App::new();
+app.add_module(ScheduleRunnerModule::run_frames(10));
+assert_eq!(app.run(), AppExit::Success);
+```
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct ScheduleRunnerModule {
+    pub run_mode: RunMode,
+}
+
+impl ScheduleRunnerModule {
+    /// Update the [`App`] once.
+    pub fn run_once() -> Self {
+        Self {
+            run_mode: RunMode::Once,
+        }
+    }
+
+    /// Update the [`App`] the specified amount of times.
+    pub fn run_frames(frames: u64) -> Self {
+        Self {
+            run_mode: RunMode::Frames(frames),
+        }
+    }
+
+    /// Update the [`App`] in a loop, waiting at least `wait` per frame.
+    pub fn run_loop(wait: Duration) -> Self {
+        Self {
+            run_mode: RunMode::Loop { wait: Some(wait) },
+        }
+    }
+
+    /// Update the [`App`] in a loop at most `fps` times per second.
+    /// Returns `None`, if the rate isn't positive or doesn't result in a representable, non-zero frame time.
+    pub fn run_at_fps(fps: f64) -> Option<Self> {
+        Duration::try_from_secs_f64(fps.recip())
+            .ok()
+            .filter(|wait| fps > 0.0 && !wait.is_zero())
+            .map(Self::run_loop)
+    }
+}
+
+impl Module for ScheduleRunnerModule {
+    fn setup(self, app: &mut App) {
+        app.world
+            .add_resource(ScheduleRunnerSettings {
+                run_mode: self.run_mode,
+            })
+            .unwrap();
+        app.set_runner(schedule_runner);
+    }
+}
+
+fn schedule_runner(mut app: App) -> AppExit {
+    let run_mode = app
+        .world
+        .get_resource::<ScheduleRunnerSettings>()
+        .unwrap()
+        .run_mode;
+    app.startup();
+
+    let exit = match run_mode {
+        RunMode::Once => app.update(),
+        RunMode::Frames(frames) => (0..frames).find_map(|_| app.update()),
+        RunMode::Loop { wait } => loop {
+            let start = Instant::now();
+            if let Some(exit) = app.update() {
+                break Some(exit);
+            }
+            if let Some(remaining) = wait.and_then(|wait| wait.checked_sub(start.elapsed())) {
+                thread::sleep(remaining);
+            }
+        },
+    };
+
+    app.shutdown();
+    exit.unwrap_or_default()
+}
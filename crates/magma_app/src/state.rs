@@ -0,0 +1,186 @@
+use std::{
+    any::{Any, type_name},
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+pub use StateSchedule::{OnEnter, OnExit, OnTransition};
+
+use crate::{App, World, error::ScheduleError, system::Schedule};
+
+/// Types, which can be used as states. This is implemented for every type fulfilling the bounds.
+pub trait States: Clone + Eq + Hash + Debug + Send + Sync + 'static {}
+
+impl<T: Clone + Eq + Hash + Debug + Send + Sync + 'static> States for T {}
+
+/**
+Resource containing the current state of type `S`. It is added by [`App::init_state`].
+
+The state can't be changed directly. Use [`NextState`] instead.
+
+# Example
+
+```
+use magma_app::{App, World, schedule::Update, state::{NextState, OnEnter, State, in_state}, system::SystemConfig};
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum GameState {
+    Menu,
+    InGame,
+}
+
+let mut app = App::new();
+app.init_state(GameState::Menu);
+app.add_state_systems(OnEnter(GameState::InGame), &[(spawn_level, "spawn_level", &[])])
+    .unwrap();
+app.add_system::<Update>(SystemConfig::new("start_game", start_game).run_if(in_state(GameState::Menu)))
+    .unwrap();
+
+fn start_game(world: &World) {
+    world.get_resource_mut::<NextState<GameState>>().unwrap().set(GameState::InGame);
+}
+
+fn spawn_level(_world: &World) {}
+```
+*/
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct State<S: States>(pub(crate) S);
+
+impl<S: States> State<S> {
+    /// Get the current state.
+    pub fn get(&self) -> &S {
+        &self.0
+    }
+}
+
+/// Resource for changing the [`State`] of type `S`. The transition is applied in the [`StateTransition`](crate::schedule::StateTransition) schedule.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NextState<S: States>(pub(crate) Option<S>);
+
+impl<S: States> Default for NextState<S> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<S: States> NextState<S> {
+    /// Transition to `state` at the next transition point. Transitioning to the current state does nothing.
+    pub fn set(&mut self, state: S) {
+        self.0 = Some(state);
+    }
+
+    /// Cancel the pending transition.
+    pub fn reset(&mut self) {
+        self.0 = None;
+    }
+
+    /// Get the pending transition.
+    pub fn pending(&self) -> Option<&S> {
+        self.0.as_ref()
+    }
+}
+
+/// The schedules run when a [`State`] changes. [`OnExit`] runs first, then [`OnTransition`] and [`OnEnter`] last.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum StateSchedule<S: States> {
+    /// Runs when the state is entered. This also runs for the initial state before the first frame.
+    OnEnter(S),
+    /// Runs when the state is exited.
+    OnExit(S),
+    /// Runs when transitioning between two specific states.
+    OnTransition { from: S, to: S },
+}
+
+/// Returns a condition, which is `true` if the current [`State`] is `state`.
+pub fn in_state<S: States>(state: S) -> impl Fn(&World) -> bool + Send + Sync {
+    move |world| {
+        world
+            .get_resource::<State<S>>()
+            .is_ok_and(|current| current.0 == state)
+    }
+}
+
+/// Type erased [`StateSchedules`], so the states of all types can be stored in the [`App`].
+pub(crate) trait AnyStateSchedules: Send + Sync {
+    fn apply_transition(&self, app: &App);
+
+    fn validate(&self) -> Result<(), ScheduleError>;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+pub(crate) struct StateSchedules<S: States> {
+    schedules: HashMap<StateSchedule<S>, Schedule>,
+    entered: AtomicBool,
+}
+
+impl<S: States> Default for StateSchedules<S> {
+    fn default() -> Self {
+        Self {
+            schedules: HashMap::new(),
+            entered: AtomicBool::new(false),
+        }
+    }
+}
+
+impl<S: States> StateSchedules<S> {
+    pub(crate) fn schedule_mut(&mut self, schedule: StateSchedule<S>) -> &mut Schedule {
+        self.schedules
+            .entry(schedule)
+            .or_insert_with_key(|schedule| {
+                // Names identify the schedule in errors, diagnostics and commands, so every state schedule needs its own.
+                // They are created once per state schedule, so leaking them is fine.
+                let name = format!("{}::{schedule:?}", type_name::<StateSchedule<S>>());
+                Schedule::new(name.leak())
+            })
+    }
+
+    fn run(&self, app: &App, schedule: &StateSchedule<S>) {
+        if let Some(schedule) = self.schedules.get(schedule) {
+            schedule.run(&app.world, app.error_handler).unwrap();
+            app.apply_commands();
+        }
+    }
+}
+
+impl<S: States> AnyStateSchedules for StateSchedules<S> {
+    fn apply_transition(&self, app: &App) {
+        let current = app.world.get_resource::<State<S>>().unwrap().0.clone();
+        if !self.entered.swap(true, Ordering::Relaxed) {
+            self.run(app, &OnEnter(current.clone()));
+        }
+
+        let Some(next) = app
+            .world
+            .get_resource_mut::<NextState<S>>()
+            .unwrap()
+            .0
+            .take()
+        else {
+            return;
+        };
+        if next == current {
+            return;
+        }
+        self.run(app, &OnExit(current.clone()));
+        self.run(
+            app,
+            &OnTransition {
+                from: current,
+                to: next.clone(),
+            },
+        );
+        app.world.get_resource_mut::<State<S>>().unwrap().0 = next.clone();
+        self.run(app, &OnEnter(next));
+    }
+
+    fn validate(&self) -> Result<(), ScheduleError> {
+        self.schedules.values().try_for_each(Schedule::validate)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{App, AppExit, World};
+
+/// Must be implemented on labels used to identify [`SubApp`]s.
+pub trait AppLabel {}
+
+/**
+An [`App`] owned by another [`App`], with its own [`World`], schedules and modules.
+
+Every frame, after the main schedules of the owning [`App`] have run, the extract function copies the required data from the main [`World`] into the sub-app's world. Then the sub-app is updated.
+
+# Example
+
+```
+use magma_app::{App, World, sub_app::{AppLabel, SubApp}};
+
+struct RenderApp;
+
+impl AppLabel for RenderApp {}
+
+let mut app = App::new();
+app.insert_sub_app::<RenderApp>(SubApp::new(App::new(), extract));
+app.update();
+
+fn extract(main_world: &World, render_world: &World) {
+    // copy data into the render world
+}
+```
+*/
+pub struct SubApp {
+    pub app: App,
+    extract: BoxedExtract,
+}
+
+/// A boxed extract function. Any `Fn(&World, &World)`, which is [`Send`] and [`Sync`], can be used, including capturing closures.
+pub type BoxedExtract = Box<dyn Fn(&World, &World) + Send + Sync>;
+
+impl SubApp {
+    /// Create a new [`SubApp`] from an [`App`] and an extract function, which receives the main world and the sub-app's world.
+    pub fn new(app: App, extract: impl Fn(&World, &World) + Send + Sync + 'static) -> Self {
+        Self {
+            app,
+            extract: Box::new(extract),
+        }
+    }
+
+    /// Set the extract function of the [`SubApp`].
+    pub fn set_extract(&mut self, extract: impl Fn(&World, &World) + Send + Sync + 'static) {
+        self.extract = Box::new(extract);
+    }
+
+    /// Run the extract function with the main world.
+    pub fn extract(&self, main_world: &World) {
+        (self.extract)(main_world, &self.app.world);
+    }
+
+    /// Extract from the main world and update the sub-app. Returns the [`AppExit`] event, if one has been sent in the sub-app.
+    pub fn update(&self, main_world: &World) -> Option<AppExit> {
+        self.extract(main_world);
+        self.app.update()
+    }
+}
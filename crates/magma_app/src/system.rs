@@ -0,0 +1,514 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    error::Error,
+    panic::{AssertUnwindSafe, catch_unwind, resume_unwind},
+    sync::{
+        Mutex, OnceLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+
+use magma_ecs::rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use tracing::{error, info_span, warn};
+
+use crate::{
+    World,
+    command::with_system,
+    diagnostic::SystemTimings,
+    error::{ScheduleError, SystemError},
+};
+
+/// The result of a fallible system.
+pub type SystemResult = Result<(), Box<dyn Error>>;
+
+/// A boxed system. Any `Fn(&World)`, which is [`Send`] and [`Sync`] and returns a [`SystemOutput`], can be used as a system.
+pub type BoxedSystem = Box<dyn Fn(&World) -> SystemResult + Send + Sync>;
+
+/// Handles the errors returned by systems. See [`App::set_error_handler`](crate::App::set_error_handler).
+pub type ErrorHandler = fn(&World, &SystemError) -> ErrorAction;
+
+/**
+The return type of a system. Systems can either return nothing or a [`Result`], whose error is passed to the [`ErrorHandler`] of the [`App`](crate::App).
+
+# Example
+
+```
+use magma_app::{App, World, schedule::Update, system::SystemConfig};
+
+let mut app = App::new();
+app.add_system::<Update>(SystemConfig::new("increment", increment)).unwrap();
+
+fn increment(world: &World) -> Result<(), Box<dyn std::error::Error>> {
+    *world.get_resource_mut::<u32>()? += 1;
+    Ok(())
+}
+```
+*/
+pub trait SystemOutput {
+    fn into_result(self) -> SystemResult;
+}
+
+impl SystemOutput for () {
+    fn into_result(self) -> SystemResult {
+        Ok(())
+    }
+}
+
+impl<E: Into<Box<dyn Error>>> SystemOutput for Result<(), E> {
+    fn into_result(self) -> SystemResult {
+        self.map_err(Into::into)
+    }
+}
+
+/// What to do with a system after its error has been handled.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum ErrorAction {
+    /// Keep running the system.
+    #[default]
+    Continue,
+    /// Disable the system. It can be enabled again with [`App::set_system_enabled`](crate::App::set_system_enabled).
+    Disable,
+}
+
+/// The default [`ErrorHandler`]. Panics with the name of the failing system and its schedule.
+pub fn panic_on_error(_world: &World, error: &SystemError) -> ErrorAction {
+    panic!("{error}");
+}
+
+/// An [`ErrorHandler`], which logs the error and keeps running the system.
+pub fn log_error(_world: &World, error: &SystemError) -> ErrorAction {
+    error!(system = error.system, schedule = error.schedule, "{error}");
+    ErrorAction::Continue
+}
+
+/// An [`ErrorHandler`], which logs the error and disables the failing system.
+pub fn disable_on_error(_world: &World, error: &SystemError) -> ErrorAction {
+    warn!(
+        system = error.system,
+        schedule = error.schedule,
+        "{error}, disabling it"
+    );
+    ErrorAction::Disable
+}
+
+/// A run condition. Systems and sets only run if all of their conditions return `true`. See [`condition`](crate::condition).
+pub type Condition = Box<dyn Fn(&World) -> bool + Send + Sync>;
+
+/**
+The configuration of a system, which can be added to an [`App`](crate::App) with [`App::add_system`](crate::App::add_system).
+
+Dependencies are specified by name and can refer to other systems or sets in the same schedule.
+They are validated when the schedule is built, so systems and sets added by other modules can be referenced.
+
+Systems can be functions or closures, which allows capturing configuration at runtime.
+Use [`SystemConfig::with_state`] for systems with local persistent state.
+
+# Example
+
+```
+use magma_app::{App, World, condition::resource_exists, schedule::Update, system::SystemConfig};
+
+let mut app = App::new();
+app.add_system::<Update>(
+    SystemConfig::new("move_players", move_players)
+        .after("input")
+        .in_set("physics")
+        .run_if(resource_exists::<u32>),
+)
+.unwrap();
+
+fn move_players(_world: &World) {}
+```
+*/
+pub struct SystemConfig {
+    pub(crate) name: &'static str,
+    pub(crate) run: BoxedSystem,
+    pub(crate) after: Vec<&'static str>,
+    pub(crate) before: Vec<&'static str>,
+    pub(crate) sets: Vec<&'static str>,
+    pub(crate) conditions: Vec<Condition>,
+    pub(crate) enabled: AtomicBool,
+}
+
+impl SystemConfig {
+    /// Create a new [`SystemConfig`]. The name must be unique within the schedule.
+    pub fn new<O: SystemOutput>(
+        name: &'static str,
+        run: impl Fn(&World) -> O + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name,
+            run: Box::new(move |world| run(world).into_result()),
+            after: vec![],
+            before: vec![],
+            sets: vec![],
+            conditions: vec![],
+            enabled: AtomicBool::new(true),
+        }
+    }
+
+    /**
+    Create a new [`SystemConfig`] for a system with local persistent state. The state is passed to every run of the system.
+
+    # Example
+
+    ```
+    use magma_app::{App, World, schedule::Update, system::SystemConfig};
+
+    let mut app = App::new();
+    app.add_system::<Update>(SystemConfig::with_state(
+        "count_frames",
+        0_u64,
+        |_world: &World, frames: &mut u64| *frames += 1,
+    ))
+    .unwrap();
+    ```
+    */
+    pub fn with_state<T: Send + 'static, O: SystemOutput>(
+        name: &'static str,
+        state: T,
+        run: impl Fn(&World, &mut T) -> O + Send + Sync + 'static,
+    ) -> Self {
+        let state = Mutex::new(state);
+        Self::new(name, move |world| run(world, &mut state.lock().unwrap()))
+    }
+
+    /// Run the system after the system or set with the specified name.
+    pub fn after(mut self, name: &'static str) -> Self {
+        self.after.push(name);
+        self
+    }
+
+    /// Run the system before the system or set with the specified name.
+    pub fn before(mut self, name: &'static str) -> Self {
+        self.before.push(name);
+        self
+    }
+
+    /// Add the system to a set. The ordering and conditions of the set apply to the system as well.
+    pub fn in_set(mut self, set: &'static str) -> Self {
+        self.sets.push(set);
+        self
+    }
+
+    /// Only run the system if `condition` returns `true`.
+    pub fn run_if(mut self, condition: impl Fn(&World) -> bool + Send + Sync + 'static) -> Self {
+        self.conditions.push(Box::new(condition));
+        self
+    }
+}
+
+/**
+The configuration of a set of systems, which can be added to an [`App`](crate::App) with [`App::configure_set`](crate::App::configure_set).
+
+Systems are added to a set with [`SystemConfig::in_set`]. A set doesn't have to be configured to be used.
+
+# Example
+
+```
+use magma_app::{App, condition::resource_exists, schedule::Update, system::SetConfig};
+
+let mut app = App::new();
+app.configure_set::<Update>(
+    SetConfig::new("physics")
+        .after("input")
+        .run_if(resource_exists::<u32>),
+)
+.unwrap();
+```
+*/
+pub struct SetConfig {
+    pub(crate) name: &'static str,
+    pub(crate) after: Vec<&'static str>,
+    pub(crate) before: Vec<&'static str>,
+    pub(crate) conditions: Vec<Condition>,
+}
+
+impl SetConfig {
+    /// Create a new [`SetConfig`].
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            after: vec![],
+            before: vec![],
+            conditions: vec![],
+        }
+    }
+
+    /// Run the members of the set after the system or set with the specified name.
+    pub fn after(mut self, name: &'static str) -> Self {
+        self.after.push(name);
+        self
+    }
+
+    /// Run the members of the set before the system or set with the specified name.
+    pub fn before(mut self, name: &'static str) -> Self {
+        self.before.push(name);
+        self
+    }
+
+    /// Only run the members of the set if `condition` returns `true`. The condition is evaluated once per run of the schedule.
+    pub fn run_if(mut self, condition: impl Fn(&World) -> bool + Send + Sync + 'static) -> Self {
+        self.conditions.push(Box::new(condition));
+        self
+    }
+}
+
+struct Graph {
+    /// indices of the systems, which can run in parallel
+    stages: Vec<Vec<usize>>,
+    /// indices of the configured sets of every system
+    system_sets: Vec<Vec<usize>>,
+}
+
+/// The systems and sets of a schedule. The execution graph is built lazily and cached until the schedule changes.
+pub(crate) struct Schedule {
+    name: &'static str,
+    systems: Vec<SystemConfig>,
+    sets: Vec<SetConfig>,
+    graph: OnceLock<Result<Graph, ScheduleError>>,
+}
+
+impl Schedule {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            systems: vec![],
+            sets: vec![],
+            graph: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn add_system(&mut self, system: SystemConfig) {
+        self.systems.push(system);
+        self.graph = OnceLock::new();
+    }
+
+    /// Removing a system changes the structure of the schedule, so the graph has to be rebuilt.
+    pub(crate) fn remove_system(&mut self, name: &str) -> Result<SystemConfig, ScheduleError> {
+        let index = self.index_of(name)?;
+        self.graph = OnceLock::new();
+        Ok(self.systems.remove(index))
+    }
+
+    /// Replacing a system keeps its ordering and conditions, so the graph stays valid.
+    pub(crate) fn replace_system(
+        &mut self,
+        name: &str,
+        run: BoxedSystem,
+    ) -> Result<(), ScheduleError> {
+        let index = self.index_of(name)?;
+        self.systems[index].run = run;
+        Ok(())
+    }
+
+    pub(crate) fn set_system_enabled(
+        &mut self,
+        name: &str,
+        enabled: bool,
+    ) -> Result<(), ScheduleError> {
+        let index = self.index_of(name)?;
+        self.systems[index]
+            .enabled
+            .store(enabled, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub(crate) fn system_enabled(&self, name: &str) -> Result<bool, ScheduleError> {
+        self.index_of(name)
+            .map(|index| self.systems[index].enabled.load(Ordering::Relaxed))
+    }
+
+    /// Configuring a set again replaces its previous configuration.
+    pub(crate) fn configure_set(&mut self, set: SetConfig) {
+        self.sets.retain(|configured| configured.name != set.name);
+        self.sets.push(set);
+        self.graph = OnceLock::new();
+    }
+
+    /// Build the execution graph, if it isn't cached already.
+    pub(crate) fn validate(&self) -> Result<(), ScheduleError> {
+        self.graph().map(|_| ())
+    }
+
+    pub(crate) fn run(&self, world: &World, handler: ErrorHandler) -> Result<(), ScheduleError> {
+        let graph = self.graph()?;
+        let span = info_span!("schedule", name = self.name);
+        let _entered = span.enter();
+        // set conditions are evaluated at most once per run
+        let set_results: Vec<OnceLock<bool>> = self.sets.iter().map(|_| OnceLock::new()).collect();
+        // the resource must not be locked while the systems run
+        let timed = world.get_resource::<SystemTimings>().is_ok();
+
+        for stage in &graph.stages {
+            stage.par_iter().for_each(|&index| {
+                let system = &self.systems[index];
+                if !system.enabled.load(Ordering::Relaxed) {
+                    return;
+                }
+                let sets_pass = graph.system_sets[index].iter().all(|&set| {
+                    *set_results[set].get_or_init(|| {
+                        self.sets[set]
+                            .conditions
+                            .iter()
+                            .all(|condition| condition(world))
+                    })
+                });
+                if !sets_pass || !system.conditions.iter().all(|condition| condition(world)) {
+                    return;
+                }
+                let _system_span =
+                    info_span!(parent: &span, "system", name = system.name).entered();
+                let start = timed.then(Instant::now);
+                let run = || with_system(self.name, index, || (system.run)(world));
+                let result = match catch_unwind(AssertUnwindSafe(run)) {
+                    Ok(result) => result,
+                    Err(payload) => {
+                        error!(
+                            system = system.name,
+                            schedule = self.name,
+                            "system `{}` in schedule `{}` panicked: {}",
+                            system.name,
+                            self.name,
+                            panic_message(&*payload)
+                        );
+                        resume_unwind(payload);
+                    }
+                };
+                if let Some(start) = start {
+                    world.get_resource::<SystemTimings>().unwrap().record(
+                        self.name,
+                        system.name,
+                        start.elapsed(),
+                    );
+                }
+                if let Err(error) = result {
+                    let error = SystemError {
+                        system: system.name,
+                        schedule: self.name,
+                        error,
+                    };
+                    if handler(world, &error) == ErrorAction::Disable {
+                        system.enabled.store(false, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn index_of(&self, name: &str) -> Result<usize, ScheduleError> {
+        self.systems
+            .iter()
+            .position(|system| system.name == name)
+            .ok_or(ScheduleError::SystemNotFound)
+    }
+
+    fn graph(&self) -> Result<&Graph, ScheduleError> {
+        self.graph
+            .get_or_init(|| self.build())
+            .as_ref()
+            .map_err(|error| *error)
+    }
+
+    fn build(&self) -> Result<Graph, ScheduleError> {
+        let mut indices = HashMap::new();
+        for (index, system) in self.systems.iter().enumerate() {
+            if indices.insert(system.name, index).is_some() {
+                return Err(ScheduleError::DuplicateSystem {
+                    system: system.name,
+                });
+            }
+        }
+        let mut members: HashMap<&str, Vec<usize>> = HashMap::new();
+        for set in &self.sets {
+            members.entry(set.name).or_default();
+        }
+        for (index, system) in self.systems.iter().enumerate() {
+            for set in &system.sets {
+                members.entry(*set).or_default().push(index);
+            }
+        }
+
+        let resolve = |system: &'static str, dependency: &'static str| match (
+            indices.get(dependency),
+            members.get(dependency),
+        ) {
+            (Some(&index), _) => Ok(vec![index]),
+            (None, Some(members)) => Ok(members.clone()),
+            (None, None) => Err(ScheduleError::UnknownDependency { system, dependency }),
+        };
+
+        // edges[a] contains b, if a has to run before b
+        let mut edges = vec![vec![]; self.systems.len()];
+        let mut system_sets = vec![vec![]; self.systems.len()];
+        for (index, system) in self.systems.iter().enumerate() {
+            let mut after = system.after.clone();
+            let mut before = system.before.clone();
+            for name in &system.sets {
+                if let Some(set) = self.sets.iter().position(|set| set.name == *name) {
+                    after.extend(&self.sets[set].after);
+                    before.extend(&self.sets[set].before);
+                    system_sets[index].push(set);
+                }
+            }
+            for dependency in after {
+                for other in resolve(system.name, dependency)? {
+                    edges[other].push(index);
+                }
+            }
+            for dependency in before {
+                for other in resolve(system.name, dependency)? {
+                    edges[index].push(other);
+                }
+            }
+        }
+
+        let mut in_degrees = vec![0; self.systems.len()];
+        for targets in &edges {
+            for &target in targets {
+                in_degrees[target] += 1;
+            }
+        }
+        let mut stages = vec![];
+        let mut stage: Vec<usize> = (0..self.systems.len())
+            .filter(|&index| in_degrees[index] == 0)
+            .collect();
+        let mut sorted = 0;
+        while !stage.is_empty() {
+            sorted += stage.len();
+            let mut next = vec![];
+            for &index in &stage {
+                for &target in &edges[index] {
+                    in_degrees[target] -= 1;
+                    if in_degrees[target] == 0 {
+                        next.push(target);
+                    }
+                }
+            }
+            stages.push(stage);
+            stage = next;
+        }
+        if sorted < self.systems.len() {
+            let index = in_degrees.iter().position(|&degree| degree > 0).unwrap();
+            return Err(ScheduleError::DependencyCycle {
+                system: self.systems[index].name,
+            });
+        }
+
+        Ok(Graph {
+            stages,
+            system_sets,
+        })
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic payload")
+}
@@ -0,0 +1,278 @@
+use std::time::{Duration, Instant};
+
+/**
+Resource containing the frame timing of the [`App`](crate::App). It is updated at the start of every frame.
+
+The "virtual" clock ([`Time::delta`], [`Time::elapsed`]) can be paused and scaled.
+The "real" clock ([`Time::real_delta`], [`Time::real_elapsed`]) is never affected by that.
+
+# Example
+
+```
+use magma_app::{World, time::Time};
+
+fn move_system(world: &World) {
+    let time = world.get_resource::<Time>().unwrap();
+    let distance = 5.0 * time.delta_secs();
+    // move something by distance
+}
+```
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Time {
+    startup: Instant,
+    last_update: Option<Instant>,
+    delta: Duration,
+    elapsed: Duration,
+    real_delta: Duration,
+    real_elapsed: Duration,
+    frame_count: u64,
+    paused: bool,
+    scale: f64,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self::new(Instant::now())
+    }
+}
+
+impl Time {
+    /// Create a new [`Time`] with the specified startup [`Instant`].
+    pub fn new(startup: Instant) -> Self {
+        Self {
+            startup,
+            last_update: None,
+            delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            real_delta: Duration::ZERO,
+            real_elapsed: Duration::ZERO,
+            frame_count: 0,
+            paused: false,
+            scale: 1.0,
+        }
+    }
+
+    /// Update the clocks with the current [`Instant`]. This is done by the [`App`](crate::App) every frame.
+    pub fn update(&mut self) {
+        self.update_with_instant(Instant::now());
+    }
+
+    /// Update the clocks with the specified [`Instant`]. The first update only starts the clocks.
+    pub fn update_with_instant(&mut self, instant: Instant) {
+        self.real_delta = match self.last_update {
+            Some(last_update) => instant.saturating_duration_since(last_update),
+            None => Duration::ZERO,
+        };
+        self.real_elapsed += self.real_delta;
+        self.last_update = Some(instant);
+
+        self.delta = if self.paused {
+            Duration::ZERO
+        } else {
+            self.real_delta.mul_f64(self.scale)
+        };
+        self.elapsed += self.delta;
+        self.frame_count += 1;
+    }
+
+    /// Get the scaled time passed since the last frame.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Get the scaled time passed since the last frame in seconds.
+    pub fn delta_secs(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// Get the scaled time passed since startup.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Get the scaled time passed since startup in seconds.
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    /// Get the unscaled time passed since the last frame.
+    pub fn real_delta(&self) -> Duration {
+        self.real_delta
+    }
+
+    /// Get the unscaled time passed since startup.
+    pub fn real_elapsed(&self) -> Duration {
+        self.real_elapsed
+    }
+
+    /// Get the [`Instant`] the clocks were started at.
+    pub fn startup(&self) -> Instant {
+        self.startup
+    }
+
+    /// Get the [`Instant`] of the last update.
+    pub fn last_update(&self) -> Option<Instant> {
+        self.last_update
+    }
+
+    /// Get the amount of frames that have been updated.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Is the scaled clock paused?
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pause the scaled clock. [`Time::delta`] will be zero until [`Time::unpause`] is called.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Unpause the scaled clock.
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    /// Get the factor, which the scaled clock runs at relative to the real clock.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /**
+    Set the factor, which the scaled clock runs at relative to the real clock.
+
+    # Panics
+
+    Panics if `scale` is negative or not finite.
+    */
+    pub fn set_scale(&mut self, scale: f64) {
+        assert!(
+            scale.is_finite() && scale >= 0.0,
+            "time scale must be finite and not negative"
+        );
+        self.scale = scale;
+    }
+}
+
+/**
+Resource driving the [`FixedUpdate`](crate::schedule::FixedUpdate) schedule.
+
+Every frame the [`Time::delta`] is added to an accumulator, which is then spent in steps of [`FixedTime::step`].
+For each full step [`FixedUpdate`](crate::schedule::FixedUpdate) runs once. This means it can run zero or more times per frame.
+
+To avoid a "spiral of death", where catching up takes longer than the time that is caught up on,
+at most [`FixedTime::max_steps`] steps are run per frame. Any time exceeding that is dropped.
+
+# Example
+
+```
+use std::time::Duration;
+use magma_app::{App, time::FixedTime};
+
+let app = App::new();
+// run the fixed update at 30 Hz
+app.world
+    .get_resource_mut::<FixedTime>()
+    .unwrap()
+    .set_step(Duration::from_secs_f64(1.0 / 30.0));
+```
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FixedTime {
+    step: Duration,
+    max_steps: u32,
+    accumulator: Duration,
+}
+
+impl Default for FixedTime {
+    fn default() -> Self {
+        Self::new(Duration::from_secs_f64(1.0 / Self::DEFAULT_HZ))
+    }
+}
+
+impl FixedTime {
+    /// The default rate of the fixed update in Hz.
+    pub const DEFAULT_HZ: f64 = 60.0;
+    /// The default maximum amount of steps run per frame.
+    pub const DEFAULT_MAX_STEPS: u32 = 5;
+
+    /// Create a new [`FixedTime`] with the specified step.
+    pub fn new(step: Duration) -> Self {
+        Self {
+            step,
+            max_steps: Self::DEFAULT_MAX_STEPS,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    /// Create a new [`FixedTime`], which runs at the specified rate in Hz.
+    /// Returns `None`, if the rate isn't positive or doesn't result in a representable, non-zero step.
+    pub fn from_hz(hz: f64) -> Option<Self> {
+        Duration::try_from_secs_f64(hz.recip())
+            .ok()
+            .filter(|step| hz > 0.0 && !step.is_zero())
+            .map(Self::new)
+    }
+
+    /// Create the [`FixedTime`] with the specified maximum amount of steps per frame.
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Get the duration of a single step.
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Set the duration of a single step.
+    pub fn set_step(&mut self, step: Duration) {
+        self.step = step;
+    }
+
+    /// Get the maximum amount of steps run per frame.
+    pub fn max_steps(&self) -> u32 {
+        self.max_steps
+    }
+
+    /// Set the maximum amount of steps run per frame.
+    pub fn set_max_steps(&mut self, max_steps: u32) {
+        self.max_steps = max_steps;
+    }
+
+    /// Get the time, which has been accumulated but not yet spent on a step.
+    pub fn accumulator(&self) -> Duration {
+        self.accumulator
+    }
+
+    /**
+    Get the interpolation factor between the last and the next step.
+
+    This is in the range `0.0..1.0` and can be used by render code to interpolate between two fixed updates. With a zero step it is `0.0`.
+    */
+    pub fn alpha(&self) -> f32 {
+        if self.step.is_zero() {
+            0.0
+        } else {
+            self.accumulator.as_secs_f32() / self.step.as_secs_f32()
+        }
+    }
+
+    /// Add `delta` to the accumulator. The accumulator is capped at [`FixedTime::max_steps`] steps.
+    pub fn accumulate(&mut self, delta: Duration) {
+        self.accumulator = (self.accumulator + delta).min(self.step * self.max_steps);
+    }
+
+    /// Spend one step from the accumulator. Returns `true` if a step was available.
+    pub fn expend(&mut self) -> bool {
+        if self.step.is_zero() || self.accumulator < self.step {
+            false
+        } else {
+            self.accumulator -= self.step;
+            true
+        }
+    }
+}
@@ -1,8 +1,11 @@
-use std::time::Instant;
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
 
 use magma_app::{
-    App, World,
-    schedule::{Startup, Update},
+    App, AppExit, World,
+    schedule::{Shutdown, Startup, Update},
 };
 
 #[test]
@@ -39,6 +42,34 @@ fn event_systems() {
     app.run();
 }
 
+#[test]
+fn app_exit() {
+    let mut app = App::new();
+    app.world.add_resource(0_u32).unwrap();
+    app.add_systems::<Update>(&[(exit_after_frames, "exit_after_frames", &[])])
+        .unwrap();
+    app.add_systems::<Shutdown>(&[(shutdown, "shutdown", &[])])
+        .unwrap();
+
+    assert_eq!(app.run(), AppExit::from_code(3));
+    assert!(SHUTDOWN.load(Ordering::Relaxed));
+}
+
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
+fn exit_after_frames(world: &World) {
+    let mut frames = world.get_resource_mut::<u32>().unwrap();
+    *frames += 1;
+    if *frames == 10 {
+        world.send_event(AppExit::from_code(3)).unwrap();
+    }
+}
+
+fn shutdown(world: &World) {
+    assert_eq!(*world.get_resource::<u32>().unwrap(), 10);
+    SHUTDOWN.store(true, Ordering::Relaxed);
+}
+
 fn system_startup(world: &World) {
     let time = Instant::now();
 
@@ -70,13 +101,14 @@ fn event_system(world: &World) {
     world.send_event(Event).unwrap();
 }
 
-fn test_runner(app: App) {
+fn test_runner(app: App) -> AppExit {
     app.run_schedule::<Startup>().unwrap();
     for _ in 0..10 {
         app.run_schedule::<Update>().unwrap();
         app.process_events();
     }
     assert_eq!(20, *app.world.get_resource::<u32>().unwrap());
+    AppExit::Success
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,185 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use magma_app::{
+    App, World,
+    change::{Added, Changed, Tick, Tracked},
+    command::Commands,
+    error::ChangeError,
+    schedule::Update,
+    system::SystemConfig,
+};
+
+struct Health(u32);
+struct Score(u32);
+
+#[test]
+fn tracked_marks_changes() {
+    let mut app = App::new();
+    app.add_tracked_resource(Score(0));
+    assert!(
+        !app.world
+            .get_resource::<Tracked<Score>>()
+            .unwrap()
+            .is_changed()
+    );
+    app.apply_commands();
+    assert!(
+        app.world
+            .get_resource::<Tracked<Score>>()
+            .unwrap()
+            .is_changed()
+    );
+    let added = Tick::now(&app.world);
+
+    let _ = app.world.get_resource_mut::<Tracked<Score>>().unwrap().0;
+    app.apply_commands();
+    let score = app.world.get_resource::<Tracked<Score>>().unwrap();
+    assert!(!score.is_changed_since(added));
+    assert!(!score.is_changed());
+    drop(score);
+
+    app.world
+        .get_resource_mut::<Tracked<Score>>()
+        .unwrap()
+        .bypass_change_detection()
+        .0 = 5;
+    app.apply_commands();
+    assert!(
+        !app.world
+            .get_resource::<Tracked<Score>>()
+            .unwrap()
+            .is_changed_since(added)
+    );
+
+    app.world.get_resource_mut::<Tracked<Score>>().unwrap().0 = 0;
+    app.apply_commands();
+    let score = app.world.get_resource::<Tracked<Score>>().unwrap();
+    assert!(score.is_changed_since(added));
+    assert!(!score.is_changed_since(Tick::now(&app.world)));
+    assert!(score.is_changed());
+    drop(score);
+
+    app.apply_commands();
+    assert!(
+        !app.world
+            .get_resource::<Tracked<Score>>()
+            .unwrap()
+            .is_changed()
+    );
+}
+
+static ADDED: AtomicUsize = AtomicUsize::new(0);
+static CHANGED: AtomicUsize = AtomicUsize::new(0);
+
+fn count_changes(world: &World, last_run: &mut Tick) {
+    ADDED.store(
+        Added::<Health>::since(world, *last_run).unwrap().len(),
+        Ordering::Relaxed,
+    );
+    CHANGED.store(
+        Changed::<Health>::since(world, *last_run).unwrap().len(),
+        Ordering::Relaxed,
+    );
+    *last_run = Tick::now(world);
+}
+
+#[test]
+fn added_and_changed_filters() {
+    let mut app = App::new();
+    app.world.register_component::<Health>();
+    app.track_changes::<Health>();
+    app.add_system::<Update>(SystemConfig::with_state(
+        "count_changes",
+        Tick::default(),
+        count_changes,
+    ))
+    .unwrap();
+    let first = app.world.create_entity((Health(10),)).unwrap();
+    app.world.create_entity((Health(10),)).unwrap();
+
+    app.update();
+    assert_eq!(ADDED.load(Ordering::Relaxed), 2);
+    assert_eq!(CHANGED.load(Ordering::Relaxed), 2);
+
+    app.update();
+    assert_eq!(ADDED.load(Ordering::Relaxed), 0);
+    assert_eq!(CHANGED.load(Ordering::Relaxed), 0);
+
+    app.world.get_component_mut::<Health>(first).unwrap().0 = 5;
+    app.update();
+    assert_eq!(ADDED.load(Ordering::Relaxed), 0);
+    assert_eq!(CHANGED.load(Ordering::Relaxed), 1);
+    assert_eq!(app.world.get_component::<Health>(first).unwrap().0, 5);
+}
+
+#[test]
+fn removed_components_are_not_reported() {
+    let mut app = App::new();
+    app.world.register_component::<Health>();
+    app.track_changes::<Health>();
+    let before = Tick::now(&app.world);
+    let entity = app.world.create_entity((Health(10),)).unwrap();
+    app.apply_commands();
+    assert!(Added::<Health>::matches(&app.world, entity, before).unwrap());
+
+    Commands::new(&app.world).remove::<Health>(entity);
+    app.apply_commands();
+    assert!(!Changed::<Health>::matches(&app.world, entity, before).unwrap());
+    assert!(
+        Changed::<Health>::since(&app.world, before)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn untracked_component() {
+    let mut app = App::new();
+    app.world.register_component::<Health>();
+
+    assert_eq!(
+        Changed::<Health>::since(&app.world, Tick::default()),
+        Err(ChangeError::NotTracked {
+            component: std::any::type_name::<Health>()
+        })
+    );
+}
+
+static SCORE_CHANGES: AtomicUsize = AtomicUsize::new(0);
+
+fn increase_score(world: &World) {
+    let mut score = world.get_resource_mut::<Tracked<Score>>().unwrap();
+    if score.0 < 2 {
+        score.0 += 1;
+    }
+}
+
+fn count_score_changes(world: &World, last_run: &mut Tick) {
+    if world
+        .get_resource::<Tracked<Score>>()
+        .unwrap()
+        .is_changed_since(*last_run)
+    {
+        SCORE_CHANGES.fetch_add(1, Ordering::Relaxed);
+    }
+    *last_run = Tick::now(world);
+}
+
+#[test]
+fn resource_is_changed() {
+    let mut app = App::new();
+    app.add_tracked_resource(Score(0));
+    app.add_systems::<Update>(&[(increase_score, "increase_score", &[])])
+        .unwrap();
+    app.add_system::<Update>(
+        SystemConfig::with_state("count_score_changes", Tick::default(), count_score_changes)
+            .after("increase_score"),
+    )
+    .unwrap();
+
+    for _ in 0..5 {
+        app.update();
+    }
+    // added, then changed twice
+    assert_eq!(SCORE_CHANGES.load(Ordering::Relaxed), 3);
+}
@@ -0,0 +1,139 @@
+use std::{thread, time::Duration};
+
+use magma_app::{
+    App, World,
+    command::Commands,
+    entities::Entity,
+    schedule::{PostUpdate, Update},
+};
+
+struct Enemy;
+struct Dead;
+
+#[test]
+fn commands_are_deferred() {
+    let mut app = App::new();
+    app.world.register_component::<Enemy>();
+    app.add_systems::<Update>(&[(spawn_enemy, "spawn_enemy", &[])])
+        .unwrap();
+
+    app.update();
+    assert_eq!(app.world.query::<(Enemy,)>().unwrap().len(), 1);
+    app.update();
+    assert_eq!(app.world.query::<(Enemy,)>().unwrap().len(), 2);
+}
+
+#[test]
+fn commands_applied_between_schedules() {
+    let mut app = App::new();
+    app.world.register_component::<Enemy>();
+    app.world.register_component::<Dead>();
+    app.world.create_entity((Enemy,)).unwrap();
+    app.world.create_entity((Enemy,)).unwrap();
+    app.add_systems::<Update>(&[(kill_enemies, "kill_enemies", &[])])
+        .unwrap();
+    app.add_systems::<PostUpdate>(&[(despawn_dead, "despawn_dead", &[])])
+        .unwrap();
+
+    app.update();
+    assert_eq!(app.world.query::<(Enemy,)>().unwrap().len(), 0);
+}
+
+#[test]
+fn remove_component() {
+    let mut app = App::new();
+    app.world.register_component::<Enemy>();
+    app.world.register_component::<Dead>();
+    let entity = app.world.create_entity((Enemy, Dead)).unwrap();
+
+    Commands::new(&app.world).remove::<Dead>(entity);
+    assert_eq!(app.world.query::<(Dead,)>().unwrap().len(), 1);
+    app.apply_commands();
+    assert_eq!(app.world.query::<(Dead,)>().unwrap().len(), 0);
+    assert_eq!(app.world.query::<(Enemy,)>().unwrap().len(), 1);
+}
+
+#[test]
+fn deterministic_order() {
+    let mut app = App::new();
+    app.world.add_resource(Vec::<&str>::new()).unwrap();
+    // the systems run in parallel, but the second one finishes first
+    app.add_systems::<Update>(&[
+        (slow_first, "slow_first", &[]),
+        (fast_second, "fast_second", &[]),
+    ])
+    .unwrap();
+
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(
+        *app.world.get_resource::<Vec<&str>>().unwrap(),
+        ["first", "second"].repeat(5)
+    );
+}
+
+#[test]
+fn commands_without_app() {
+    let mut world = World::new();
+    world.register_component::<Enemy>();
+
+    let commands = Commands::new(&world);
+    commands.spawn((Enemy,));
+    commands.spawn((Enemy,));
+    drop(commands);
+    // there is no app to apply the commands later
+    assert_eq!(world.query::<(Enemy,)>().unwrap().len(), 2);
+
+    let enemy: Entity = (&world.query::<(Enemy,)>().unwrap()[0]).into();
+    Commands::new(&world).despawn(enemy);
+    assert_eq!(world.query::<(Enemy,)>().unwrap().len(), 1);
+}
+
+fn spawn_enemy(world: &World) {
+    let commands = Commands::new(world);
+    commands.spawn((Enemy,));
+    assert_eq!(commands.len(), 1);
+    // the entity doesn't exist until the commands are applied
+    assert_eq!(
+        world.query::<(Enemy,)>().unwrap().len(),
+        world
+            .get_resource::<magma_app::time::Time>()
+            .unwrap()
+            .frame_count() as usize
+            - 1
+    );
+}
+
+fn kill_enemies(world: &World) {
+    let commands = Commands::new(world);
+    world
+        .query::<(Enemy,)>()
+        .unwrap()
+        .iter()
+        .for_each(|enemy| commands.insert(enemy.into(), (Dead,)));
+}
+
+fn despawn_dead(world: &World) {
+    let commands = Commands::new(world);
+    world
+        .query::<(Dead,)>()
+        .unwrap()
+        .iter()
+        .for_each(|dead| commands.despawn(dead.into()));
+}
+
+fn slow_first(world: &World) {
+    let commands = Commands::new(world);
+    thread::sleep(Duration::from_millis(5));
+    commands.add(|world| world.get_resource_mut::<Vec<&str>>().unwrap().push("first"));
+}
+
+fn fast_second(world: &World) {
+    Commands::new(world).add(|world| {
+        world
+            .get_resource_mut::<Vec<&str>>()
+            .unwrap()
+            .push("second")
+    });
+}
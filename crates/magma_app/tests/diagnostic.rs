@@ -0,0 +1,117 @@
+use std::{thread, time::Duration};
+
+use magma_app::{
+    App, World,
+    diagnostic::{Diagnostic, Diagnostics, DiagnosticsModule, DumpFormat, FPS, FRAME_TIME},
+    event::EventWriter,
+    schedule::Update,
+};
+
+#[derive(Clone)]
+struct Ping;
+
+#[test]
+fn rolling_history() {
+    let mut diagnostic = Diagnostic::new(3);
+    for value in [1.0, 2.0, 3.0, 4.0] {
+        diagnostic.add_measurement(value);
+    }
+
+    assert_eq!(
+        diagnostic.history().collect::<Vec<_>>(),
+        vec![2.0, 3.0, 4.0]
+    );
+    assert_eq!(diagnostic.latest(), Some(4.0));
+    assert_eq!(diagnostic.average(), Some(3.0));
+}
+
+#[test]
+fn record_frame_diagnostics() {
+    let mut app = App::new();
+    app.add_module(DiagnosticsModule::default());
+    app.register_event::<Ping>();
+    app.add_systems::<Update>(&[(send_ping, "send_ping", &[])])
+        .unwrap();
+
+    app.update();
+    thread::sleep(Duration::from_millis(1));
+    app.update();
+
+    let diagnostics = app.world.get_resource::<Diagnostics>().unwrap();
+    assert!(diagnostics.get(FRAME_TIME).unwrap().latest().unwrap() >= 1.0);
+    assert!(diagnostics.get(FPS).unwrap().latest().unwrap() > 0.0);
+    let pings = diagnostics
+        .iter()
+        .find(|(name, _)| name.starts_with("event/") && name.ends_with("Ping"))
+        .unwrap()
+        .1;
+    assert_eq!(pings.history().collect::<Vec<_>>(), vec![1.0, 1.0]);
+    let send_ping = diagnostics
+        .iter()
+        .find(|(name, _)| name.starts_with("system/") && name.ends_with("/send_ping"))
+        .unwrap()
+        .1;
+    assert_eq!(send_ping.history().count(), 2);
+}
+
+#[test]
+fn system_timings_disabled() {
+    let mut app = App::new();
+    app.add_module(DiagnosticsModule {
+        system_timings: false,
+        ..Default::default()
+    });
+    app.add_systems::<Update>(&[(noop, "noop", &[])]).unwrap();
+
+    app.update();
+
+    let diagnostics = app.world.get_resource::<Diagnostics>().unwrap();
+    assert!(
+        diagnostics
+            .iter()
+            .all(|(name, _)| !name.starts_with("system/"))
+    );
+}
+
+#[test]
+fn export_formats() {
+    let mut diagnostics = Diagnostics::new(10);
+    diagnostics.add_measurement(FPS, 60.0);
+    diagnostics.add_measurement(FPS, 30.0);
+
+    assert_eq!(
+        diagnostics.to_csv(),
+        "name,sample,value\n\"fps\",0,60\n\"fps\",1,30\n"
+    );
+    assert_eq!(
+        diagnostics.to_json(),
+        "{\"fps\":{\"latest\":30,\"average\":45,\"history\":[60,30]}}"
+    );
+}
+
+#[test]
+fn dump_on_shutdown() {
+    let path = std::env::temp_dir().join("magma_app_diagnostics_dump.csv");
+    let mut app = App::new();
+    app.add_module(DiagnosticsModule {
+        dump: Some((path.clone(), DumpFormat::Csv)),
+        ..Default::default()
+    });
+
+    app.startup();
+    thread::sleep(Duration::from_millis(1));
+    app.update();
+    app.update();
+    app.shutdown();
+
+    let csv = std::fs::read_to_string(&path).unwrap();
+    assert!(csv.starts_with("name,sample,value\n"));
+    assert!(csv.contains("\"fps\","));
+    std::fs::remove_file(path).unwrap();
+}
+
+fn send_ping(world: &World) {
+    EventWriter::<Ping>::new(world).send(Ping).unwrap();
+}
+
+fn noop(_world: &World) {}
@@ -0,0 +1,135 @@
+use std::any::type_name;
+
+use magma_app::{
+    App, World,
+    error::ScheduleError,
+    event::{EventDispatchMode, EventReader, EventWriter, Events},
+    schedule::{PostUpdate, Update},
+    system::SystemConfig,
+};
+
+#[derive(Clone, PartialEq, Debug)]
+struct Input(u32);
+
+#[test]
+fn events_double_buffered() {
+    let mut events = Events::default();
+    let mut reader = EventReader::default();
+    events.send(Input(1));
+    events.update();
+    events.send(Input(2));
+
+    assert_eq!(reader.len(&events), 2);
+    assert_eq!(
+        reader.read(&events).collect::<Vec<_>>(),
+        [&Input(1), &Input(2)]
+    );
+    assert!(reader.is_empty(&events));
+
+    events.update();
+    events.send(Input(3));
+    events.update();
+    assert_eq!(events.iter().collect::<Vec<_>>(), [&Input(3)]);
+    assert_eq!(reader.read(&events).collect::<Vec<_>>(), [&Input(3)]);
+}
+
+#[test]
+fn independent_readers() {
+    let mut events = Events::default();
+    let mut first = EventReader::default();
+    events.send(Input(1));
+    let mut second = events.reader_from_now();
+    events.send(Input(2));
+
+    assert_eq!(first.read(&events).count(), 2);
+    assert_eq!(second.read(&events).collect::<Vec<_>>(), [&Input(2)]);
+}
+
+#[test]
+fn read_in_later_frame() {
+    let mut app = App::new();
+    app.register_event::<Input>();
+    app.world.add_resource(Vec::<u32>::new()).unwrap();
+    app.world.add_resource(0_u32).unwrap();
+    app.add_system::<Update>(SystemConfig::new("send_input", send_input))
+        .unwrap();
+    app.add_system::<PostUpdate>(SystemConfig::with_state(
+        "read_input",
+        EventReader::<Input>::default(),
+        |world: &World, reader: &mut EventReader<Input>| {
+            let events = world.get_resource::<Events<Input>>().unwrap();
+            world
+                .get_resource_mut::<Vec<u32>>()
+                .unwrap()
+                .extend(reader.read(&events).map(|input| input.0));
+        },
+    ))
+    .unwrap();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    // the input of the last frame is read in the next frame
+    assert_eq!(*app.world.get_resource::<Vec<u32>>().unwrap(), [1, 2]);
+}
+
+fn send_input(world: &World) {
+    let mut frame = world.get_resource_mut::<u32>().unwrap();
+    *frame += 1;
+    EventWriter::new(world).send(Input(*frame)).unwrap();
+}
+
+#[test]
+fn event_system_order() {
+    let mut app = App::new();
+    app.set_event_dispatch_mode(EventDispatchMode::Sequential);
+    app.register_event::<Input>();
+    app.register_event::<Resized>();
+    app.register_event::<Moved>();
+    app.world.add_resource(Vec::<&'static str>::new()).unwrap();
+    app.order_event_systems::<Resized, Moved>().unwrap();
+    app.order_event_systems::<Moved, Input>().unwrap();
+    assert_eq!(
+        app.order_event_systems::<Input, Resized>(),
+        Err(ScheduleError::EventOrderCycle {
+            event: type_name::<Input>()
+        })
+    );
+
+    app.add_event_system::<Input>(SystemConfig::new("log_input", |world: &World| {
+        log(world, "input")
+    }))
+    .unwrap();
+    app.add_event_system::<Resized>(SystemConfig::new("log_resized", |world: &World| {
+        log(world, "resized")
+    }))
+    .unwrap();
+    app.add_event_system::<Moved>(SystemConfig::new("log_moved", |world: &World| {
+        log(world, "moved")
+    }))
+    .unwrap();
+
+    app.world.send_event(Input(1)).unwrap();
+    app.world.send_event(Moved).unwrap();
+    app.world.send_event(Resized).unwrap();
+    app.process_events();
+
+    assert_eq!(
+        *app.world.get_resource::<Vec<&'static str>>().unwrap(),
+        ["resized", "moved", "input"]
+    );
+}
+
+#[derive(Clone)]
+struct Resized;
+
+#[derive(Clone)]
+struct Moved;
+
+fn log(world: &World, entry: &'static str) {
+    world
+        .get_resource_mut::<Vec<&'static str>>()
+        .unwrap()
+        .push(entry);
+}
@@ -0,0 +1,137 @@
+use magma_app::{
+    App, World,
+    command::Commands,
+    error::HierarchyError,
+    hierarchy::{
+        Children, HierarchyEvent, Parent, children_of, descendants_of, parent_of, remove_parent,
+        set_parent,
+    },
+    schedule::Update,
+};
+
+struct Node;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.world.register_component::<Node>();
+    app
+}
+
+#[test]
+fn parent_and_children_consistent() {
+    let app = app();
+    let parent = app.world.create_entity((Node,)).unwrap();
+    let other = app.world.create_entity((Node,)).unwrap();
+    let child = app.world.create_entity((Node,)).unwrap();
+
+    set_parent(&app.world, child, parent).unwrap();
+    assert_eq!(parent_of(&app.world, child), Some(parent));
+    assert_eq!(children_of(&app.world, parent), [child]);
+    assert_eq!(
+        app.world.get_component::<Parent>(child).unwrap().get(),
+        parent
+    );
+
+    set_parent(&app.world, child, other).unwrap();
+    assert_eq!(parent_of(&app.world, child), Some(other));
+    assert!(children_of(&app.world, parent).is_empty());
+    assert!(app.world.get_component::<Children>(parent).is_err());
+    assert!(
+        app.world
+            .get_component::<Children>(other)
+            .unwrap()
+            .contains(child)
+    );
+
+    remove_parent(&app.world, child);
+    assert_eq!(parent_of(&app.world, child), None);
+    assert!(children_of(&app.world, other).is_empty());
+}
+
+#[test]
+fn reject_cycles() {
+    let app = app();
+    let root = app.world.create_entity((Node,)).unwrap();
+    let child = app.world.create_entity((Node,)).unwrap();
+    let grandchild = app.world.create_entity((Node,)).unwrap();
+    set_parent(&app.world, child, root).unwrap();
+    set_parent(&app.world, grandchild, child).unwrap();
+
+    assert_eq!(
+        set_parent(&app.world, root, grandchild),
+        Err(HierarchyError::Cycle)
+    );
+    assert_eq!(
+        set_parent(&app.world, root, root),
+        Err(HierarchyError::Cycle)
+    );
+    assert_eq!(descendants_of(&app.world, root), [child, grandchild]);
+}
+
+#[test]
+fn recursive_despawn() {
+    let mut app = app();
+    let root = app.world.create_entity((Node,)).unwrap();
+    let child = app.world.create_entity((Node,)).unwrap();
+    let grandchild = app.world.create_entity((Node,)).unwrap();
+    let sibling = app.world.create_entity((Node,)).unwrap();
+    set_parent(&app.world, child, root).unwrap();
+    set_parent(&app.world, grandchild, child).unwrap();
+    set_parent(&app.world, sibling, root).unwrap();
+
+    Commands::new(&app.world).despawn(child);
+    app.apply_commands();
+
+    assert_eq!(app.world.query::<(Node,)>().unwrap().len(), 2);
+    assert_eq!(children_of(&app.world, root), [sibling]);
+
+    app.add_systems::<Update>(&[(despawn_roots, "despawn_roots", &[])])
+        .unwrap();
+    app.update();
+    assert_eq!(app.world.query::<(Node,)>().unwrap().len(), 0);
+}
+
+#[test]
+fn spawn_children_and_events() {
+    let mut app = app();
+    app.world
+        .add_resource(Vec::<HierarchyEvent>::new())
+        .unwrap();
+    let parent = app.world.create_entity((Node,)).unwrap();
+    app.add_event_systems::<HierarchyEvent>(&[(record_events, "record_events", &[])])
+        .unwrap();
+
+    let commands = Commands::new(&app.world);
+    commands.spawn_child(parent, (Node,));
+    commands.spawn_child(parent, (Node,));
+    drop(commands);
+    app.apply_commands();
+    app.process_events();
+
+    let children = children_of(&app.world, parent);
+    assert_eq!(children.len(), 2);
+    assert_eq!(
+        *app.world.get_resource::<Vec<HierarchyEvent>>().unwrap(),
+        children
+            .iter()
+            .map(|&child| HierarchyEvent::ChildAdded { child, parent })
+            .collect::<Vec<_>>()
+    );
+}
+
+fn despawn_roots(world: &World) {
+    let commands = Commands::new(world);
+    world
+        .query::<(Node, Children)>()
+        .unwrap()
+        .iter()
+        .for_each(|root| commands.despawn(root.into()));
+}
+
+fn record_events(world: &World) {
+    let events = world.poll_events::<HierarchyEvent>().unwrap().to_vec();
+    world
+        .get_resource_mut::<Vec<HierarchyEvent>>()
+        .unwrap()
+        .extend(events);
+}
@@ -0,0 +1,115 @@
+use std::sync::Mutex;
+
+use magma_app::{
+    App, World,
+    command::Commands,
+    entities::Entity,
+    hierarchy::{self, Parent},
+    hook::HookKind,
+    schedule::Update,
+};
+
+struct Health(u32);
+struct Shield;
+
+/// The lifecycle changes recorded by the hooks in order.
+#[derive(Default)]
+struct Log(Mutex<Vec<(&'static str, Entity)>>);
+
+fn record(world: &World, change: &'static str, entity: Entity) {
+    world
+        .get_resource::<Log>()
+        .unwrap()
+        .0
+        .lock()
+        .unwrap()
+        .push((change, entity));
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.world.register_component::<Health>();
+    app.world.register_component::<Shield>();
+    app.world.add_resource(Log::default()).unwrap();
+    app.add_component_hook::<Health>(HookKind::OnAdd, |world, entity| {
+        record(world, "add", entity)
+    });
+    app.add_component_hook::<Health>(HookKind::OnInsert, |world, entity| {
+        record(world, "insert", entity)
+    });
+    app.add_component_hook::<Health>(HookKind::OnRemove, |world, entity| {
+        // the component can still be read
+        assert!(world.get_component::<Health>(entity).unwrap().0 > 0);
+        record(world, "remove", entity)
+    });
+    app
+}
+
+fn log(app: &App) -> Vec<(&'static str, Entity)> {
+    std::mem::take(&mut app.world.get_resource::<Log>().unwrap().0.lock().unwrap())
+}
+
+#[test]
+fn insert_and_remove_hooks() {
+    let app = app();
+    let entity = app.world.create_entity((Shield,)).unwrap();
+
+    Commands::new(&app.world).insert(entity, (Health(10),));
+    app.apply_commands();
+    assert_eq!(log(&app), vec![("add", entity), ("insert", entity)]);
+
+    // replacing the component doesn't add it
+    Commands::new(&app.world).insert(entity, (Health(5), Shield));
+    app.apply_commands();
+    assert_eq!(log(&app), vec![("insert", entity)]);
+
+    Commands::new(&app.world).remove::<Health>(entity);
+    app.apply_commands();
+    assert_eq!(log(&app), vec![("remove", entity)]);
+
+    // removing a missing component doesn't run hooks
+    Commands::new(&app.world).remove::<Health>(entity);
+    app.apply_commands();
+    assert!(log(&app).is_empty());
+
+    // inserting it again adds it again
+    Commands::new(&app.world).insert(entity, (Health(1),));
+    app.apply_commands();
+    assert_eq!(log(&app), vec![("add", entity), ("insert", entity)]);
+}
+
+#[test]
+fn spawn_and_despawn_hooks() {
+    let mut app = app();
+    app.add_systems::<Update>(&[(spawn, "spawn", &[])]).unwrap();
+
+    app.update();
+    let entity: Entity = app.world.query::<(Health,)>().unwrap()[0].into();
+    assert_eq!(log(&app), vec![("add", entity), ("insert", entity)]);
+
+    Commands::new(&app.world).despawn(entity);
+    app.apply_commands();
+    assert!(log(&app).contains(&("remove", entity)));
+}
+
+fn spawn(world: &World) {
+    if world.query::<(Health,)>().unwrap().is_empty() {
+        Commands::new(world).spawn((Health(10),));
+    }
+}
+
+#[test]
+fn recursive_despawn_runs_hooks() {
+    let mut app = app();
+    app.add_component_hook::<Parent>(HookKind::OnRemove, |world, entity| {
+        record(world, "remove_parent", entity)
+    });
+    let parent = app.world.create_entity((Shield,)).unwrap();
+    let child = app.world.create_entity((Health(10),)).unwrap();
+    hierarchy::set_parent(&app.world, child, parent).unwrap();
+    log(&app);
+
+    hierarchy::despawn_recursive(&app.world, parent).unwrap();
+    // the hooks run in the order they have been registered
+    assert_eq!(log(&app), vec![("remove", child), ("remove_parent", child)]);
+}
@@ -1,4 +1,11 @@
-use magma_app::{App, module::Module, schedule::Startup};
+use std::any::type_name;
+
+use magma_app::{
+    App,
+    error::ModuleError,
+    module::{Dependencies, Module, ModuleGroup, ModuleGroupBuilder},
+    schedule::Startup,
+};
 use magma_ecs::World;
 
 #[test]
@@ -8,6 +15,7 @@ fn add_module() {
     app.run_schedule::<Startup>().unwrap();
 }
 
+#[derive(Default)]
 pub struct TestModule;
 
 impl Module for TestModule {
@@ -21,3 +29,160 @@ impl Module for TestModule {
 fn test_system(world: &World) {
     assert_eq!(*world.get_resource::<u32>().unwrap(), 10)
 }
+
+#[test]
+fn module_dependencies() {
+    let mut app = App::new();
+    app.add_module(DependentModule);
+    assert_eq!(*app.world.get_resource::<u32>().unwrap(), 11);
+}
+
+#[test]
+fn module_conflict() {
+    let mut app = App::new();
+    app.add_module(TestModule);
+    assert_eq!(
+        app.try_add_module(ConflictingModule),
+        Err(ModuleError::Conflict {
+            module: type_name::<ConflictingModule>(),
+            conflict: type_name::<TestModule>(),
+        })
+    );
+}
+
+#[test]
+fn missing_module() {
+    let mut app = App::new();
+    assert_eq!(
+        app.try_add_module(RequiringModule),
+        Err(ModuleError::MissingDependency {
+            module: type_name::<RequiringModule>(),
+            dependency: type_name::<TestModule>(),
+        })
+    );
+}
+
+#[test]
+fn module_cycle() {
+    let mut app = App::new();
+    assert!(matches!(
+        app.try_add_module(CycleModuleA),
+        Err(ModuleError::DependencyCycle { .. })
+    ));
+}
+
+#[derive(Default)]
+pub struct DependentModule;
+
+impl Module for DependentModule {
+    fn setup(self, app: &mut App) {
+        // TestModule has been set up before
+        *app.world.get_resource_mut::<u32>().unwrap() += 1;
+    }
+
+    fn dependencies(&self, dependencies: &mut Dependencies) {
+        dependencies.add::<TestModule>();
+    }
+}
+
+pub struct ConflictingModule;
+
+impl Module for ConflictingModule {
+    fn setup(self, _app: &mut App) {}
+
+    fn dependencies(&self, dependencies: &mut Dependencies) {
+        dependencies.conflict::<TestModule>();
+    }
+}
+
+pub struct RequiringModule;
+
+impl Module for RequiringModule {
+    fn setup(self, _app: &mut App) {}
+
+    fn dependencies(&self, dependencies: &mut Dependencies) {
+        dependencies.require::<TestModule>();
+    }
+}
+
+#[derive(Default)]
+pub struct CycleModuleA;
+
+impl Module for CycleModuleA {
+    fn setup(self, _app: &mut App) {}
+
+    fn dependencies(&self, dependencies: &mut Dependencies) {
+        dependencies.add::<CycleModuleB>();
+    }
+}
+
+#[derive(Default)]
+pub struct CycleModuleB;
+
+impl Module for CycleModuleB {
+    fn setup(self, _app: &mut App) {}
+
+    fn dependencies(&self, dependencies: &mut Dependencies) {
+        dependencies.add::<CycleModuleA>();
+    }
+}
+
+#[test]
+fn module_lifecycle() {
+    let mut app = App::new();
+    app.add_module(LifecycleModule);
+    // added after LifecycleModule, but visible in its finish hook
+    app.add_module(TestModule);
+
+    app.finish();
+    assert_eq!(*app.world.get_resource::<u32>().unwrap(), 20);
+
+    app.shutdown();
+    assert_eq!(*app.world.get_resource::<u32>().unwrap(), 0);
+}
+
+pub struct LifecycleModule;
+
+impl Module for LifecycleModule {
+    fn setup(self, _app: &mut App) {}
+
+    fn finish(app: &mut App) {
+        *app.world.get_resource_mut::<u32>().unwrap() *= 2;
+    }
+
+    fn cleanup(app: &mut App) {
+        *app.world.get_resource_mut::<u32>().unwrap() = 0;
+    }
+}
+
+#[test]
+fn module_group() {
+    let mut app = App::new();
+    app.add_modules(TestModules);
+    assert_eq!(*app.world.get_resource::<u32>().unwrap(), 11);
+}
+
+#[test]
+fn module_group_configuration() {
+    let mut app = App::new();
+    app.add_modules(
+        TestModules
+            .build()
+            .disable::<DependentModule>()
+            .add_after::<TestModule>(LifecycleModule),
+    );
+    assert_eq!(*app.world.get_resource::<u32>().unwrap(), 10);
+
+    app.finish();
+    assert_eq!(*app.world.get_resource::<u32>().unwrap(), 20);
+}
+
+pub struct TestModules;
+
+impl ModuleGroup for TestModules {
+    fn build(self) -> ModuleGroupBuilder {
+        ModuleGroupBuilder::new()
+            .add_module(TestModule)
+            .add_module(DependentModule)
+    }
+}
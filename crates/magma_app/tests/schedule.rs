@@ -0,0 +1,334 @@
+use std::{
+    error::Error,
+    time::{Duration, Instant},
+};
+
+use magma_app::{
+    App, AppSchedule, World,
+    condition::{not, on_timer, resource_equals, resource_exists},
+    error::{ScheduleError, SystemError},
+    schedule::{First, Last, PostStartup, Startup, Update},
+    system::{ErrorAction, SetConfig, SystemConfig},
+    time::Time,
+};
+
+struct LateUpdate;
+
+impl AppSchedule for LateUpdate {}
+
+struct PreStartup;
+
+impl AppSchedule for PreStartup {}
+
+struct Unordered;
+
+impl AppSchedule for Unordered {}
+
+#[test]
+fn custom_schedule_order() {
+    let mut app = App::new();
+    app.world.add_resource(Vec::<&'static str>::new()).unwrap();
+    app.insert_schedule_after::<Update, LateUpdate>().unwrap();
+    app.insert_schedule_before::<Startup, PreStartup>().unwrap();
+
+    app.add_systems::<PreStartup>(&[(log_pre_startup, "log_pre_startup", &[])])
+        .unwrap();
+    app.add_systems::<Startup>(&[(log_startup, "log_startup", &[])])
+        .unwrap();
+    app.add_systems::<PostStartup>(&[(log_post_startup, "log_post_startup", &[])])
+        .unwrap();
+    app.add_systems::<First>(&[(log_first, "log_first", &[])])
+        .unwrap();
+    app.add_systems::<Update>(&[(log_update, "log_update", &[])])
+        .unwrap();
+    app.add_systems::<LateUpdate>(&[(log_late_update, "log_late_update", &[])])
+        .unwrap();
+    app.add_systems::<Last>(&[(log_last, "log_last", &[])])
+        .unwrap();
+
+    app.startup();
+    app.update();
+
+    assert_eq!(
+        *app.world.get_resource::<Vec<&'static str>>().unwrap(),
+        [
+            "pre_startup",
+            "startup",
+            "post_startup",
+            "first",
+            "update",
+            "late_update",
+            "last"
+        ]
+    );
+}
+
+#[test]
+fn move_schedule() {
+    let mut app = App::new();
+    app.world.add_resource(Vec::<&'static str>::new()).unwrap();
+    app.insert_schedule_after::<Update, LateUpdate>().unwrap();
+    app.insert_schedule_before::<First, LateUpdate>().unwrap();
+
+    app.add_systems::<First>(&[(log_first, "log_first", &[])])
+        .unwrap();
+    app.add_systems::<LateUpdate>(&[(log_late_update, "log_late_update", &[])])
+        .unwrap();
+    app.update();
+
+    assert_eq!(
+        *app.world.get_resource::<Vec<&'static str>>().unwrap(),
+        ["late_update", "first"]
+    );
+}
+
+#[test]
+fn insert_schedule_unordered_target() {
+    let mut app = App::new();
+    app.register_schedule::<Unordered>();
+
+    assert_eq!(
+        app.insert_schedule_after::<Unordered, LateUpdate>(),
+        Err(ScheduleError::ScheduleNotOrdered)
+    );
+}
+
+#[test]
+fn system_set_ordering() {
+    let mut app = App::new();
+    app.world.add_resource(Vec::<&'static str>::new()).unwrap();
+    app.configure_set::<Update>(SetConfig::new("late").after("early"))
+        .unwrap();
+    app.add_system::<Update>(SystemConfig::new("log_last", log_last).in_set("late"))
+        .unwrap();
+    app.add_system::<Update>(
+        SystemConfig::new("log_update", log_update)
+            .in_set("late")
+            .before("log_last"),
+    )
+    .unwrap();
+    app.add_system::<Update>(SystemConfig::new("log_first", log_first).in_set("early"))
+        .unwrap();
+
+    app.run_schedule::<Update>().unwrap();
+
+    assert_eq!(
+        *app.world.get_resource::<Vec<&'static str>>().unwrap(),
+        ["first", "update", "last"]
+    );
+}
+
+#[test]
+fn run_conditions() {
+    let mut app = App::new();
+    app.world.add_resource(Vec::<&'static str>::new()).unwrap();
+    app.world.add_resource(1_u32).unwrap();
+    app.configure_set::<Update>(SetConfig::new("disabled").run_if(resource_exists::<f32>))
+        .unwrap();
+    app.add_system::<Update>(
+        SystemConfig::new("log_first", log_first).run_if(resource_equals(1_u32)),
+    )
+    .unwrap();
+    app.add_system::<Update>(
+        SystemConfig::new("log_update", log_update).run_if(not(resource_equals(1_u32))),
+    )
+    .unwrap();
+    app.add_system::<Update>(SystemConfig::new("log_last", log_last).in_set("disabled"))
+        .unwrap();
+
+    app.run_schedule::<Update>().unwrap();
+
+    assert_eq!(
+        *app.world.get_resource::<Vec<&'static str>>().unwrap(),
+        ["first"]
+    );
+}
+
+#[test]
+fn timer_condition() {
+    let app = App::new();
+    let startup = Instant::now();
+    *app.world.get_resource_mut::<Time>().unwrap() = Time::new(startup);
+    let condition = on_timer(Duration::from_secs(1));
+
+    let mut runs = 0;
+    for frame in 0..25 {
+        app.world
+            .get_resource_mut::<Time>()
+            .unwrap()
+            .update_with_instant(startup + Duration::from_millis(100 * frame));
+        if condition(&app.world) {
+            runs += 1;
+        }
+    }
+    assert_eq!(runs, 2);
+}
+
+#[test]
+fn unknown_dependency() {
+    let mut app = App::new();
+    app.add_system::<Update>(SystemConfig::new("log_first", log_first).after("missing"))
+        .unwrap();
+
+    assert_eq!(
+        app.run_schedule::<Update>(),
+        Err(ScheduleError::UnknownDependency {
+            system: "log_first",
+            dependency: "missing"
+        })
+    );
+}
+
+#[test]
+fn dependency_cycle() {
+    let mut app = App::new();
+    app.add_systems::<Update>(&[
+        (log_first, "log_first", &["log_last"]),
+        (log_last, "log_last", &["log_first"]),
+    ])
+    .unwrap();
+
+    assert!(matches!(
+        app.validate_schedules(),
+        Err(ScheduleError::DependencyCycle { .. })
+    ));
+}
+
+fn log(world: &World, entry: &'static str) {
+    world
+        .get_resource_mut::<Vec<&'static str>>()
+        .unwrap()
+        .push(entry);
+}
+
+fn log_pre_startup(world: &World) {
+    log(world, "pre_startup");
+}
+
+fn log_startup(world: &World) {
+    log(world, "startup");
+}
+
+fn log_post_startup(world: &World) {
+    log(world, "post_startup");
+}
+
+fn log_first(world: &World) {
+    log(world, "first");
+}
+
+fn log_update(world: &World) {
+    log(world, "update");
+}
+
+fn log_late_update(world: &World) {
+    log(world, "late_update");
+}
+
+fn log_last(world: &World) {
+    log(world, "last");
+}
+
+#[test]
+fn closure_systems() {
+    let mut app = App::new();
+    app.world.add_resource(0_u32).unwrap();
+    app.register_event::<Increase>();
+
+    let amount = 5;
+    app.add_system::<Update>(SystemConfig::new("send_increase", move |world: &World| {
+        world.send_event(Increase(amount)).unwrap();
+    }))
+    .unwrap();
+    app.add_event_system::<Increase>(SystemConfig::with_state(
+        "increase",
+        0_u32,
+        |world: &World, runs: &mut u32| {
+            *runs += 1;
+            let increase = world
+                .poll_events::<Increase>()
+                .unwrap()
+                .iter()
+                .map(|event| event.0)
+                .sum::<u32>();
+            *world.get_resource_mut::<u32>().unwrap() += increase * *runs;
+        },
+    ))
+    .unwrap();
+
+    app.update();
+    app.update();
+
+    // 5 * 1 + 5 * 2
+    assert_eq!(*app.world.get_resource::<u32>().unwrap(), 15);
+}
+
+#[derive(Clone)]
+struct Increase(u32);
+
+#[test]
+fn remove_and_replace_systems() {
+    let mut app = App::new();
+    app.world.add_resource(Vec::<&'static str>::new()).unwrap();
+    app.add_systems::<Update>(&[
+        (log_first, "first", &[]),
+        (log_update, "update", &["first"]),
+        (log_last, "last", &["update"]),
+    ])
+    .unwrap();
+
+    app.replace_system::<Update, _>("first", log_startup)
+        .unwrap();
+    app.set_system_enabled::<Update>("last", false).unwrap();
+    assert!(!app.system_enabled::<Update>("last").unwrap());
+    app.run_schedule::<Update>().unwrap();
+
+    app.remove_system::<Update>("last").unwrap();
+    let update = app.remove_system::<Update>("update").unwrap();
+    assert_eq!(
+        app.remove_system::<Update>("update").err(),
+        Some(ScheduleError::SystemNotFound)
+    );
+    app.add_system::<Update>(update).unwrap();
+    app.run_schedule::<Update>().unwrap();
+
+    assert_eq!(
+        *app.world.get_resource::<Vec<&'static str>>().unwrap(),
+        ["startup", "update", "startup", "update"]
+    );
+}
+
+#[test]
+fn system_errors() {
+    let mut app = App::new();
+    app.world.add_resource(0_u32).unwrap();
+    app.set_error_handler(count_errors);
+    app.add_system::<Update>(SystemConfig::new("fail", fail))
+        .unwrap();
+
+    app.run_schedule::<Update>().unwrap();
+    app.run_schedule::<Update>().unwrap();
+
+    assert_eq!(*app.world.get_resource::<u32>().unwrap(), 1);
+    assert!(!app.system_enabled::<Update>("fail").unwrap());
+}
+
+#[test]
+#[should_panic(expected = "system `fail` in schedule `magma_app::schedule::Update` failed")]
+fn system_error_default_handler() {
+    let mut app = App::new();
+    app.add_system::<Update>(SystemConfig::new("fail", fail))
+        .unwrap();
+    app.run_schedule::<Update>().unwrap();
+}
+
+fn fail(world: &World) -> Result<(), Box<dyn Error>> {
+    let _ = world.get_resource::<f64>()?;
+    Ok(())
+}
+
+fn count_errors(world: &World, error: &SystemError) -> ErrorAction {
+    assert_eq!(error.system, "fail");
+    *world.get_resource_mut::<u32>().unwrap() += 1;
+    ErrorAction::Disable
+}
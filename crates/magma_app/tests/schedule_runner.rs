@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use magma_app::{
+    App, AppExit, World,
+    schedule::{Shutdown, Update},
+    schedule_runner::ScheduleRunnerModule,
+    time::Time,
+};
+
+#[test]
+fn run_once() {
+    let mut app = App::new();
+    app.add_module(ScheduleRunnerModule::run_once());
+    app.add_systems::<Update>(&[(exit_on_frame_one, "exit_on_frame_one", &[])])
+        .unwrap();
+
+    assert_eq!(app.run(), AppExit::from_code(1));
+}
+
+#[test]
+fn run_frames() {
+    let mut app = App::new();
+    app.add_module(ScheduleRunnerModule::run_frames(5));
+    app.add_systems::<Shutdown>(&[(assert_five_frames, "assert_five_frames", &[])])
+        .unwrap();
+
+    assert_eq!(app.run(), AppExit::Success);
+}
+
+#[test]
+fn run_loop_until_exit() {
+    let mut app = App::new();
+    app.add_module(ScheduleRunnerModule::run_loop(Duration::from_millis(1)));
+    app.add_systems::<Update>(&[(exit_on_frame_three, "exit_on_frame_three", &[])])
+        .unwrap();
+
+    assert_eq!(app.run(), AppExit::from_code(3));
+}
+
+#[test]
+fn run_at_fps() {
+    assert_eq!(
+        ScheduleRunnerModule::run_at_fps(50.0),
+        Some(ScheduleRunnerModule::run_loop(Duration::from_millis(20)))
+    );
+    assert_eq!(ScheduleRunnerModule::run_at_fps(0.0), None);
+    assert_eq!(ScheduleRunnerModule::run_at_fps(-60.0), None);
+    assert_eq!(ScheduleRunnerModule::run_at_fps(f64::NAN), None);
+}
+
+#[test]
+fn manual_stepping() {
+    let mut app = App::new();
+    app.add_systems::<Update>(&[(exit_on_frame_three, "exit_on_frame_three", &[])])
+        .unwrap();
+    app.finish();
+    app.startup();
+
+    assert_eq!(app.update(), None);
+    assert_eq!(app.update(), None);
+    assert_eq!(app.update(), Some(AppExit::from_code(3)));
+}
+
+fn frame_count(world: &World) -> u64 {
+    world.get_resource::<Time>().unwrap().frame_count()
+}
+
+fn exit_on_frame_one(world: &World) {
+    if frame_count(world) == 1 {
+        world.send_event(AppExit::from_code(1)).unwrap();
+    }
+}
+
+fn exit_on_frame_three(world: &World) {
+    if frame_count(world) == 3 {
+        world.send_event(AppExit::from_code(3)).unwrap();
+    }
+}
+
+fn assert_five_frames(world: &World) {
+    assert_eq!(frame_count(world), 5);
+}
@@ -0,0 +1,124 @@
+use magma_app::{
+    App, World,
+    error::SystemError,
+    schedule::Update,
+    state::{NextState, OnEnter, OnExit, OnTransition, State, in_state},
+    system::{ErrorAction, SystemConfig},
+};
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum GameState {
+    Menu,
+    InGame,
+}
+
+#[test]
+fn state_transitions() {
+    let mut app = App::new();
+    app.world.add_resource(Vec::<&'static str>::new()).unwrap();
+    app.init_state(GameState::Menu);
+
+    app.add_state_systems(OnEnter(GameState::Menu), &[(enter_menu, "enter_menu", &[])])
+        .unwrap();
+    app.add_state_systems(OnExit(GameState::Menu), &[(exit_menu, "exit_menu", &[])])
+        .unwrap();
+    app.add_state_system(
+        OnTransition {
+            from: GameState::Menu,
+            to: GameState::InGame,
+        },
+        SystemConfig::new("start_game", |world: &World| log(world, "start_game")),
+    )
+    .unwrap();
+    app.add_state_systems(
+        OnEnter(GameState::InGame),
+        &[(enter_game, "enter_game", &[])],
+    )
+    .unwrap();
+    app.add_system::<Update>(
+        SystemConfig::new("in_menu", in_menu).run_if(in_state(GameState::Menu)),
+    )
+    .unwrap();
+
+    app.startup();
+    app.update();
+    app.world
+        .get_resource_mut::<NextState<GameState>>()
+        .unwrap()
+        .set(GameState::InGame);
+    app.update();
+
+    assert_eq!(
+        *app.world.get_resource::<State<GameState>>().unwrap().get(),
+        GameState::InGame
+    );
+    assert_eq!(
+        *app.world.get_resource::<Vec<&'static str>>().unwrap(),
+        [
+            "enter_menu",
+            "in_menu",
+            "exit_menu",
+            "start_game",
+            "enter_game"
+        ]
+    );
+}
+
+fn log(world: &World, entry: &'static str) {
+    world
+        .get_resource_mut::<Vec<&'static str>>()
+        .unwrap()
+        .push(entry);
+}
+
+fn enter_menu(world: &World) {
+    log(world, "enter_menu");
+}
+
+fn exit_menu(world: &World) {
+    log(world, "exit_menu");
+}
+
+fn enter_game(world: &World) {
+    log(world, "enter_game");
+}
+
+fn in_menu(world: &World) {
+    log(world, "in_menu");
+}
+
+#[test]
+fn state_schedule_names() {
+    let mut app = App::new();
+    app.world.add_resource(Vec::<&'static str>::new()).unwrap();
+    app.set_error_handler(record_schedule);
+    app.init_state(GameState::Menu);
+    app.add_state_system(OnEnter(GameState::Menu), SystemConfig::new("fail", fail))
+        .unwrap();
+    app.add_state_system(OnExit(GameState::Menu), SystemConfig::new("fail", fail))
+        .unwrap();
+    app.add_state_system(OnEnter(GameState::InGame), SystemConfig::new("fail", fail))
+        .unwrap();
+
+    app.startup();
+    app.world
+        .get_resource_mut::<NextState<GameState>>()
+        .unwrap()
+        .set(GameState::InGame);
+    app.update();
+
+    let schedules = app.world.get_resource::<Vec<&'static str>>().unwrap();
+    assert_eq!(schedules.len(), 3);
+    assert!(schedules[0].ends_with("OnEnter(Menu)"));
+    assert!(schedules[1].ends_with("OnExit(Menu)"));
+    assert!(schedules[2].ends_with("OnEnter(InGame)"));
+}
+
+fn fail(_world: &World) -> Result<(), &'static str> {
+    Err("failed")
+}
+
+fn record_schedule(world: &World, error: &SystemError) -> ErrorAction {
+    log(world, error.schedule);
+    ErrorAction::Continue
+}
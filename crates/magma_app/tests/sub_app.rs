@@ -0,0 +1,71 @@
+use magma_app::{
+    App, AppExit, World,
+    schedule::Update,
+    sub_app::{AppLabel, SubApp},
+};
+
+struct CounterApp;
+
+impl AppLabel for CounterApp {}
+
+#[test]
+fn sub_app_extract_and_update() {
+    let mut app = App::new();
+    app.world.add_resource(0_u32).unwrap();
+    app.add_systems::<Update>(&[(increment, "increment", &[])])
+        .unwrap();
+
+    let mut sub_app = App::new();
+    sub_app.world.add_resource(Vec::<u32>::new()).unwrap();
+    sub_app
+        .add_systems::<Update>(&[(exit_after_three, "exit_after_three", &[])])
+        .unwrap();
+    app.insert_sub_app::<CounterApp>(SubApp::new(sub_app, extract_counter));
+
+    assert_eq!(app.update(), None);
+    assert_eq!(app.update(), None);
+    assert_eq!(app.update(), Some(AppExit::from_code(2)));
+
+    let sub_app = app.sub_app::<CounterApp>().unwrap();
+    assert_eq!(
+        *sub_app.app.world.get_resource::<Vec<u32>>().unwrap(),
+        [1, 2, 3]
+    );
+    assert!(app.remove_sub_app::<CounterApp>().is_some());
+    assert!(app.sub_app::<CounterApp>().is_none());
+}
+
+fn increment(world: &World) {
+    *world.get_resource_mut::<u32>().unwrap() += 1;
+}
+
+fn extract_counter(main_world: &World, sub_world: &World) {
+    let counter = *main_world.get_resource::<u32>().unwrap();
+    sub_world
+        .get_resource_mut::<Vec<u32>>()
+        .unwrap()
+        .push(counter);
+}
+
+fn exit_after_three(world: &World) {
+    if world.get_resource::<Vec<u32>>().unwrap().len() == 3 {
+        world.send_event(AppExit::from_code(2)).unwrap();
+    }
+}
+
+#[test]
+fn capturing_extract() {
+    let mut app = App::new();
+    let mut sub_app = SubApp::new(App::new(), |_, _| {});
+    sub_app.app.world.add_resource(0_u32).unwrap();
+    let value = 7_u32;
+    sub_app.set_extract(move |_main_world, sub_world| {
+        *sub_world.get_resource_mut::<u32>().unwrap() = value;
+    });
+    app.insert_sub_app::<CounterApp>(sub_app);
+
+    app.update();
+
+    let sub_app = app.sub_app::<CounterApp>().unwrap();
+    assert_eq!(*sub_app.app.world.get_resource::<u32>().unwrap(), 7);
+}
@@ -0,0 +1,112 @@
+use std::time::{Duration, Instant};
+
+use magma_app::{
+    App, World,
+    schedule::FixedUpdate,
+    time::{FixedTime, Time},
+};
+
+#[test]
+fn fixed_time_steps() {
+    let mut fixed_time = FixedTime::new(Duration::from_millis(10));
+    fixed_time.accumulate(Duration::from_millis(25));
+
+    assert!(fixed_time.expend());
+    assert!(fixed_time.expend());
+    assert!(!fixed_time.expend());
+    assert!((fixed_time.alpha() - 0.5).abs() < 0.001);
+}
+
+#[test]
+fn fixed_time_from_hz() {
+    let fixed_time = FixedTime::from_hz(50.0).unwrap();
+    assert_eq!(fixed_time.step(), Duration::from_millis(20));
+
+    assert_eq!(FixedTime::from_hz(0.0), None);
+    assert_eq!(FixedTime::from_hz(-60.0), None);
+    assert_eq!(FixedTime::from_hz(f64::NAN), None);
+}
+
+#[test]
+fn fixed_time_zero_step() {
+    let mut fixed_time = FixedTime::new(Duration::ZERO);
+    fixed_time.accumulate(Duration::from_millis(10));
+
+    assert!(!fixed_time.expend());
+    assert_eq!(fixed_time.alpha(), 0.0);
+}
+
+#[test]
+fn fixed_time_max_steps() {
+    let mut fixed_time = FixedTime::new(Duration::from_millis(10)).with_max_steps(3);
+    fixed_time.accumulate(Duration::from_secs(1));
+
+    let mut steps = 0;
+    while fixed_time.expend() {
+        steps += 1;
+    }
+    assert_eq!(steps, 3);
+}
+
+#[test]
+fn fixed_update_schedule() {
+    let mut app = App::new();
+    app.world.add_resource(0_u32).unwrap();
+    app.add_systems::<FixedUpdate>(&[(count_steps, "count_steps", &[])])
+        .unwrap();
+
+    {
+        let mut fixed_time = app.world.get_resource_mut::<FixedTime>().unwrap();
+        fixed_time.set_step(Duration::from_secs(10));
+        fixed_time.accumulate(Duration::from_secs(20));
+    }
+    app.update();
+
+    assert_eq!(*app.world.get_resource::<u32>().unwrap(), 2);
+}
+
+fn count_steps(world: &World) {
+    *world.get_resource_mut::<u32>().unwrap() += 1;
+}
+
+#[test]
+fn time_update() {
+    let startup = Instant::now();
+    let mut time = Time::new(startup);
+
+    time.update_with_instant(startup);
+    assert_eq!(time.delta(), Duration::ZERO);
+
+    time.update_with_instant(startup + Duration::from_millis(100));
+    assert_eq!(time.delta(), Duration::from_millis(100));
+    assert_eq!(time.elapsed(), Duration::from_millis(100));
+    assert_eq!(time.frame_count(), 2);
+}
+
+#[test]
+fn time_pause_and_scale() {
+    let startup = Instant::now();
+    let mut time = Time::new(startup);
+    time.update_with_instant(startup);
+
+    time.pause();
+    time.update_with_instant(startup + Duration::from_millis(100));
+    assert_eq!(time.delta(), Duration::ZERO);
+    assert_eq!(time.real_delta(), Duration::from_millis(100));
+
+    time.unpause();
+    time.set_scale(2.0);
+    time.update_with_instant(startup + Duration::from_millis(200));
+    assert_eq!(time.delta(), Duration::from_millis(200));
+    assert_eq!(time.elapsed(), Duration::from_millis(200));
+    assert_eq!(time.real_elapsed(), Duration::from_millis(200));
+}
+
+#[test]
+fn time_resource() {
+    let app = App::new();
+    app.update();
+    app.update();
+
+    assert_eq!(app.world.get_resource::<Time>().unwrap().frame_count(), 2);
+}
@@ -0,0 +1,143 @@
+/*!
+This crate provides the [`LogModule`], which installs a [`tracing`] subscriber for structured logging.
+
+The core crates of the Magma API emit spans and events through [`magma_app::tracing`]. Without the [`LogModule`] (or another subscriber) they are discarded.
+
+# Example
+
+```
+use magma_app::{App, tracing::info};
+use magma_log::{Level, LogModule};
+
+let mut app = App::new();
+app.add_module(LogModule {
+    level: Level::DEBUG,
+    filter: "wgpu=error,magma_winit=trace".to_owned(),
+    ..Default::default()
+});
+info!("logging is set up");
+```
+
+# Profiling
+
+With the `trace_chrome` feature enabled, the spans of schedules, systems, event processing and rendering can be written to a Chrome trace file by setting `LogModule::chrome_trace`.
+The file can be opened in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev). It is complete once the [`App`] has shut down.
+*/
+
+use std::{fs::File, path::PathBuf, sync::Mutex};
+
+use magma_app::{App, module::Module, tracing::warn};
+use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+
+pub use magma_app::tracing::Level;
+
+/**
+Module installing a global [`tracing`] subscriber, which writes to stderr and optionally to a file.
+
+If the `RUST_LOG` environment variable is set, it overrides [`LogModule::level`] and [`LogModule::filter`].
+Only one global subscriber can be installed per process. If one exists already, a warning is emitted and the existing one is kept.
+*/
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LogModule {
+    /// The default level for all targets.
+    pub level: Level,
+    /// Comma separated per-target directives, e.g. `"wgpu=error,magma_app=debug"`.
+    /// These take precedence over [`LogModule::level`]. See [`EnvFilter`] for the syntax.
+    pub filter: String,
+    /// Additionally write the logs without ANSI colors to this file. The file is truncated.
+    /// If it can't be created, a warning is logged and the logs are only written to stderr.
+    pub file: Option<PathBuf>,
+    /// Write all enabled spans as Chrome trace JSON to this file. This requires the `trace_chrome` feature, otherwise a warning is logged instead.
+    pub chrome_trace: Option<PathBuf>,
+}
+
+impl Default for LogModule {
+    fn default() -> Self {
+        Self {
+            level: Level::INFO,
+            filter: "wgpu=error,naga=warn".to_owned(),
+            file: None,
+            chrome_trace: None,
+        }
+    }
+}
+
+impl LogModule {
+    /// Build the [`EnvFilter`] of the module. `RUST_LOG` takes precedence, if it is set.
+    pub fn env_filter(&self) -> EnvFilter {
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+            EnvFilter::builder().parse_lossy(format!("{},{}", self.level, self.filter))
+        })
+    }
+}
+
+impl Module for LogModule {
+    #[cfg_attr(not(feature = "trace_chrome"), allow(unused_variables))]
+    fn setup(self, app: &mut App) {
+        let mut file_error = None;
+        let file_layer = self
+            .file
+            .as_ref()
+            .and_then(|path| match File::create(path) {
+                Ok(file) => Some(fmt::layer().with_ansi(false).with_writer(Mutex::new(file))),
+                Err(error) => {
+                    file_error = Some((path, error));
+                    None
+                }
+            });
+
+        let subscriber = tracing_subscriber::registry()
+            .with(self.env_filter())
+            .with(fmt::layer())
+            .with(file_layer);
+        #[cfg(feature = "trace_chrome")]
+        let (chrome_layer, chrome_guard) = self
+            .chrome_trace
+            .as_ref()
+            .map(|path| {
+                tracing_chrome::ChromeLayerBuilder::new()
+                    .file(path)
+                    .include_args(true)
+                    .build()
+            })
+            .unzip();
+        #[cfg(feature = "trace_chrome")]
+        let subscriber = subscriber.with(chrome_layer);
+
+        let result = subscriber.try_init();
+        // the guard is only kept, if its layer has been installed
+        #[cfg(feature = "trace_chrome")]
+        if let (Ok(()), Some(guard)) = (&result, chrome_guard) {
+            app.world
+                .add_resource(ChromeTraceGuard(Mutex::new(Some(guard))))
+                .unwrap();
+        }
+        if let Err(error) = result {
+            warn!("could not install the log subscriber: {error}");
+        }
+        // reported once the subscriber is installed, so the failure is logged as well
+        if let Some((path, error)) = file_error {
+            warn!("failed to create log file {}: {error}", path.display());
+        }
+        #[cfg(not(feature = "trace_chrome"))]
+        if let Some(path) = &self.chrome_trace {
+            warn!(
+                "not writing the chrome trace {}, because the `trace_chrome` feature is disabled",
+                path.display()
+            );
+        }
+    }
+
+    #[cfg_attr(not(feature = "trace_chrome"), allow(unused_variables))]
+    fn cleanup(app: &mut App) {
+        // the trace file is completed, when the guard is dropped
+        #[cfg(feature = "trace_chrome")]
+        if let Ok(guard) = app.world.get_resource::<ChromeTraceGuard>() {
+            guard.0.lock().unwrap().take();
+        }
+    }
+}
+
+/// Keeps the Chrome trace file open until the [`App`] shuts down.
+#[cfg(feature = "trace_chrome")]
+struct ChromeTraceGuard(Mutex<Option<tracing_chrome::FlushGuard>>);
@@ -0,0 +1,31 @@
+#![cfg(feature = "trace_chrome")]
+
+use std::fs;
+
+use magma_app::{App, AppExit, World, schedule::Update, schedule_runner::ScheduleRunnerModule};
+use magma_log::LogModule;
+
+#[test]
+fn headless_chrome_trace() {
+    let path = std::env::temp_dir().join("magma_log_chrome_trace.json");
+    let mut app = App::new();
+    app.add_module(LogModule {
+        chrome_trace: Some(path.clone()),
+        ..Default::default()
+    });
+    app.add_module(ScheduleRunnerModule::run_frames(3));
+    app.add_systems::<Update>(&[(traced_system, "traced_system", &[])])
+        .unwrap();
+
+    assert_eq!(app.run(), AppExit::Success);
+
+    let trace = fs::read_to_string(&path).unwrap();
+    assert!(trace.trim_start().starts_with('['));
+    assert!(trace.contains("\"frame\""));
+    assert!(trace.contains("\"schedule\""));
+    assert!(trace.contains("\"process_events\""));
+    assert!(trace.contains("traced_system"));
+    fs::remove_file(path).unwrap();
+}
+
+fn traced_system(_world: &World) {}
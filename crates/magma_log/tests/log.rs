@@ -0,0 +1,46 @@
+use std::{fs, panic};
+
+use magma_app::{
+    App, World,
+    schedule::Update,
+    tracing::{debug, info, warn},
+};
+use magma_log::{Level, LogModule};
+
+#[test]
+fn log_to_file() {
+    let path = std::env::temp_dir().join("magma_log_test.log");
+    let mut app = App::new();
+    app.add_module(LogModule {
+        level: Level::INFO,
+        filter: "log=warn".to_owned(),
+        file: Some(path.clone()),
+        ..Default::default()
+    });
+    app.add_systems::<Update>(&[(log_something, "log_something", &[])])
+        .unwrap();
+    app.update();
+    app.add_systems::<Update>(&[(panicking_system, "panicking_system", &[])])
+        .unwrap();
+    assert!(panic::catch_unwind(panic::AssertUnwindSafe(|| app.update())).is_err());
+
+    let logs = fs::read_to_string(&path).unwrap();
+    assert!(logs.contains("info from app"));
+    assert!(logs.contains("warning from test"));
+    assert!(!logs.contains("info from test"));
+    assert!(!logs.contains("debug from app"));
+    assert!(logs.contains("system `panicking_system` in schedule"));
+    assert!(logs.contains("panicked: oh no"));
+    fs::remove_file(path).unwrap();
+}
+
+fn log_something(_world: &World) {
+    info!(target: "app", "info from app");
+    debug!(target: "app", "debug from app");
+    info!(target: "log", "info from test");
+    warn!(target: "log", "warning from test");
+}
+
+fn panicking_system(_world: &World) {
+    panic!("oh no");
+}
@@ -1 +1,16 @@
+use magma_math::Mat4;
+use magma_transform::GlobalTransform;
 
+use crate::sync_module::SyncComponent;
+
+/// The world-space transformation matrix of a render entity, synced from the [`GlobalTransform`] of the main world entity.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct RenderTransform(pub Mat4);
+
+impl SyncComponent for GlobalTransform {
+    type Out = RenderTransform;
+
+    fn get_data(&self) -> Self::Out {
+        RenderTransform(self.compute_matrix())
+    }
+}
@@ -79,10 +79,6 @@ impl<'a> ExtractedWindows<'a> {
         self.render_entity_to_window.get_mut(render_entity).unwrap()
     }
 
-    pub fn get_render_entity(&self, entity: &Entity) -> Entity {
-        *self.entity_to_render_entity.get(entity).unwrap()
-    }
-
     pub fn iter_windows(
         &self,
     ) -> std::collections::hash_map::Values<'_, Entity, (RawHandleWrapper, Surface<'a>)> {
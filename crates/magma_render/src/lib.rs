@@ -1,26 +1,24 @@
-use std::time::Instant;
+use std::any::type_name;
 
 use feufeu::RenderState;
 use magma_app::{
-    App, AppSchedule,
-    module::Module,
+    App, AppExit, AppSchedule,
+    hook::HookKind,
+    module::{Dependencies, Module},
     rayon::join,
-    schedule::{PostUpdate, PreUpdate, Startup, Update},
-};
-use magma_windowing::{
-    ClosingWindow, Window, raw_handle::RawHandleWrapper, window_event::WindowClosed,
+    tracing::{debug, info, info_span},
 };
+use magma_transform::{GlobalTransform, TransformModule};
+use magma_windowing::{ClosingWindow, Window, raw_handle::RawHandleWrapper};
 use magma_winit::{WinitModule, WrappedApp, windows::Windows};
-use winit::{
-    application::ApplicationHandler,
-    event_loop::{ControlFlow, EventLoop},
-};
+use winit::{application::ApplicationHandler, event_loop::EventLoop};
 
 use crate::{
     extracted_windows::{ExtractedWindow, ExtractedWindows},
     render_stages::background::BackgroundStage,
+    sync_component_module::SyncComponentModule,
     sync_module::{EntityRenderEntityMap, RenderEntity, SyncModule, SyncToRenderWorld},
-    systems::drop_windows,
+    systems::drop_window,
 };
 
 pub mod components;
@@ -32,18 +30,20 @@ pub mod sync_component_module;
 pub mod sync_module;
 mod systems;
 
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 pub struct RenderModule;
 
 impl Module for RenderModule {
     fn setup(self, app: &mut magma_app::App) {
-        app.add_module(WinitModule);
         app.set_runner(rendering_update_loop);
         app.world.add_resource(RenderState::default()).unwrap();
-        app.world.add_resource(Renderer(default_renderer)).unwrap();
-        app.add_event_systems::<WindowClosed>(&[(drop_windows, "drop_windows", &[])])
+        app.world
+            .add_resource(Renderer::new(default_renderer))
             .unwrap();
+        app.add_component_hook::<Window>(HookKind::OnRemove, drop_window);
         app.register_schedule::<SyncSchedule>();
         app.add_module(SyncModule);
+        app.add_module(SyncComponentModule::<GlobalTransform>::new());
 
         let mut render_state = app.world.get_resource_mut::<RenderState>().unwrap();
 
@@ -58,6 +58,19 @@ impl Module for RenderModule {
             .add_resource(ExtractedWindows::new())
             .unwrap();
     }
+
+    fn finish(app: &mut App) {
+        // render stages are initialized once every module had the chance to configure the render state
+        app.world
+            .get_resource_mut::<RenderState>()
+            .unwrap()
+            .init_stage::<BackgroundStage>()
+            .unwrap();
+    }
+
+    fn dependencies(&self, dependencies: &mut Dependencies) {
+        dependencies.add::<WinitModule>().add::<TransformModule>();
+    }
 }
 
 pub struct RenderApp {
@@ -72,24 +85,18 @@ impl RenderApp {
     }
 }
 
-pub struct Renderer(fn(&RenderState));
+/// Resource containing the function, which renders the [`RenderState`] every frame. This can be a closure.
+pub struct Renderer(Box<dyn Fn(&RenderState) + Send + Sync>);
 
 impl Renderer {
-    pub fn new(renderer: fn(&RenderState)) -> Self {
-        Self(renderer)
+    pub fn new(renderer: impl Fn(&RenderState) + Send + Sync + 'static) -> Self {
+        Self(Box::new(renderer))
     }
 }
 
 impl ApplicationHandler for RenderApp {
     fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
         self.app.resumed(event_loop);
-        self.app
-            .app
-            .world
-            .get_resource_mut::<RenderState>()
-            .unwrap()
-            .init_stage::<BackgroundStage>()
-            .unwrap();
     }
 
     fn window_event(
@@ -111,6 +118,9 @@ impl ApplicationHandler for RenderApp {
     }
 
     fn about_to_wait(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        if self.app.exit_status().is_some() || !self.app.should_update(event_loop) {
+            return;
+        }
         self.app.winit_update(event_loop);
         self.app
             .app
@@ -138,6 +148,8 @@ impl ApplicationHandler for RenderApp {
                             .get(windows.entity_to_window.get(&window_entity.into()).unwrap())
                             .unwrap();
 
+                        let _span =
+                            info_span!("configure_surface", entity = window_entity.id()).entered();
                         let raw_handle = RawHandleWrapper::new(window);
 
                         let surface = unsafe {
@@ -170,6 +182,19 @@ impl ApplicationHandler for RenderApp {
                         };
 
                         surface.configure(render_state.get_device(), &surface_config);
+                        info!(
+                            format = ?surface_config.format,
+                            present_mode = ?surface_config.present_mode,
+                            width = surface_config.width,
+                            height = surface_config.height,
+                            "configured surface"
+                        );
+                        debug!(
+                            formats = ?surface_caps.formats,
+                            present_modes = ?surface_caps.present_modes,
+                            alpha_modes = ?surface_caps.alpha_modes,
+                            "surface capabilities"
+                        );
 
                         let render_entity = render_state
                             .render_world
@@ -207,38 +232,34 @@ impl ApplicationHandler for RenderApp {
 
         self.app.app.run_schedule::<SyncSchedule>().unwrap();
 
-        join(
-            || {
-                self.app.app.run_schedule::<PreUpdate>().unwrap();
-                self.app.app.run_schedule::<Update>().unwrap();
-                self.app.app.run_schedule::<PostUpdate>().unwrap();
-                self.app.app.process_events();
-            },
+        let (exit, _) = join(
+            || self.app.app.update(),
             || {
+                let _span = info_span!("render").entered();
                 (self.app.app.world.get_resource::<Renderer>().unwrap().0)(
                     &self.app.app.world.get_resource::<RenderState>().unwrap(),
                 )
             },
         );
+        if let Some(exit) = exit {
+            self.app.exit(event_loop, exit);
+        }
+        self.app.updated(event_loop);
     }
 }
 
-fn rendering_update_loop(app: App) {
-    app.world.create_entity((Window::new(),)).unwrap();
+fn rendering_update_loop(app: App) -> AppExit {
     let event_loop = EventLoop::new().unwrap();
-    event_loop.set_control_flow(ControlFlow::Poll);
     let mut app = RenderApp::new(app);
-    app.app.app.run_schedule::<Startup>().unwrap();
+    app.app.app.startup();
     event_loop.run_app(&mut app).unwrap();
+    app.app.app.shutdown();
+    app.app.exit_status().unwrap_or_default()
 }
 
 fn default_renderer(render_state: &RenderState) {
-    let now = Instant::now();
-    render_state.run_stage::<BackgroundStage>().unwrap();
-    println!(
-        "{}",
-        1.0 / ((1.0 / 1000000.0) * now.elapsed().as_micros() as f32)
-    );
+    info_span!("render_stage", stage = type_name::<BackgroundStage>())
+        .in_scope(|| render_state.run_stage::<BackgroundStage>().unwrap());
 }
 
 pub struct SyncSchedule;
@@ -1,18 +1,43 @@
-use std::marker::PhantomData;
+use std::{
+    any::type_name,
+    collections::HashSet,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
 
 use feufeu::RenderState;
 use magma_app::{
     World,
+    change::{Changed, Tick},
     entities::Entity,
-    module::Module,
-    rayon::iter::{IntoParallelRefIterator, ParallelIterator},
+    hook::HookKind,
+    module::{Dependencies, Module},
+    tracing::info_span,
 };
 
-use crate::sync_module::{EntityRenderEntityMap, SyncComponent, SyncSystems, SyncToRenderWorld};
+use crate::{
+    RenderModule,
+    sync_module::{
+        EntityRenderEntityMap, SyncComponent, SyncModule, SyncSystems, SyncToRenderWorld,
+    },
+};
 
+/// Module syncing the component `C` of entities marked with [`SyncToRenderWorld`] to the render world as [`SyncComponent::Out`].
+/// Components are only synced again, when they changed (see [`Changed`]). Components removed with [`Commands`](magma_app::command::Commands)
+/// are removed from the render world by a component hook. Components removed through the world directly stay in the render world, until the entity is no longer synced.
 pub struct SyncComponentModule<C: SyncComponent>(PhantomData<C>);
 
-struct EntityMap<C: SyncComponent>(Vec<Entity>, PhantomData<C>);
+impl<C: SyncComponent> SyncComponentModule<C> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: SyncComponent> Default for SyncComponentModule<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl<C: SyncComponent + 'static> Module for SyncComponentModule<C> {
     fn setup(self, app: &mut magma_app::App) {
@@ -22,69 +47,73 @@ impl<C: SyncComponent + 'static> Module for SyncComponentModule<C> {
             .render_world
             .register_component::<C::Out>();
 
-        app.world
-            .add_resource(EntityMap::<C>(vec![], PhantomData::default()))
-            .unwrap();
-
+        app.track_changes::<C>();
+        let state = Arc::new(Mutex::new(SyncState::default()));
+        let removed = state.clone();
+        app.add_component_hook::<C>(HookKind::OnRemove, move |_world, entity| {
+            removed.lock().unwrap().removed.push(entity)
+        });
         app.world
             .get_resource_mut::<SyncSystems>()
             .unwrap()
-            .push(sync_component_to_render_world_system::<C>);
+            .push(move |world: &World| {
+                sync_component_to_render_world::<C>(world, &mut state.lock().unwrap())
+            });
+    }
+
+    fn dependencies(&self, dependencies: &mut Dependencies) {
+        dependencies.add::<RenderModule>().require::<SyncModule>();
     }
 }
 
-fn sync_component_to_render_world_system<C: SyncComponent + 'static>(world: &World) {
-    let mut map = world.get_resource_mut::<EntityMap<C>>().unwrap();
-    world
-        .query::<(SyncToRenderWorld, C)>()
-        .unwrap()
-        .iter()
-        .for_each(|entity| {
-            if !map.0.contains(&entity.into()) {
-                map.0.push(entity.into());
-            }
-            let render_entity = world
-                .get_resource_mut::<EntityRenderEntityMap>()
-                .unwrap()
-                .entity_to_render_entity
-                .get(&entity.into())
-                .unwrap()
-                .to_owned();
-            world
-                .get_resource::<RenderState>()
-                .unwrap()
+/// The sync bookkeeping of a component type.
+#[derive(Default)]
+struct SyncState {
+    /// entities, which have the component synced to the render world
+    synced: HashSet<Entity>,
+    /// entities, whose component has been removed since the last sync
+    removed: Vec<Entity>,
+    last_sync: Tick,
+}
+
+fn sync_component_to_render_world<C: SyncComponent + 'static>(
+    world: &World,
+    state: &mut SyncState,
+) {
+    let _span = info_span!("sync_component", component = type_name::<C>()).entered();
+    // changes recorded after this are synced next time
+    let this_sync = Tick::now(world);
+    let render_state = world.get_resource::<RenderState>().unwrap();
+    let map = world.get_resource::<EntityRenderEntityMap>().unwrap();
+
+    // sync removed components. Despawned entities have already been removed from the map.
+    for entity in std::mem::take(&mut state.removed) {
+        if !state.synced.remove(&entity) {
+            continue;
+        }
+        if let Some(render_entity) = map.entity_to_render_entity.get(&entity) {
+            render_state
                 .render_world
-                .assign_components(
-                    (entity.get_component::<C>().unwrap().get_data(),),
-                    render_entity,
-                )
+                .delete_component::<C::Out>(*render_entity)
                 .unwrap();
-        });
-    // sync deleted components
-    if map.0.len() > world.query::<(SyncToRenderWorld, C)>().unwrap().len() {
-        map.0 = map
-            .0
-            .par_iter()
-            .filter_map(|entity| match world.get_component::<C>(*entity) {
-                Ok(_) => Some(*entity),
-                Err(_) => {
-                    world
-                        .get_resource::<RenderState>()
-                        .unwrap()
-                        .render_world
-                        .delete_component::<C::Out>(
-                            world
-                                .get_resource::<EntityRenderEntityMap>()
-                                .unwrap()
-                                .entity_to_render_entity
-                                .get(entity)
-                                .unwrap()
-                                .to_owned(),
-                        )
-                        .unwrap();
-                    None
-                }
-            })
-            .collect::<Vec<Entity>>();
+        }
     }
+
+    let changed: HashSet<Entity> = Changed::<C>::since(world, state.last_sync)
+        .unwrap()
+        .into_iter()
+        .collect();
+    for entity in &world.query::<(SyncToRenderWorld, C)>().unwrap() {
+        if !state.synced.insert(entity.into()) && !changed.contains(&entity.into()) {
+            continue;
+        }
+        let component = entity.get_component::<C>().unwrap();
+        let render_entity = map.entity_to_render_entity.get(&entity.into()).unwrap();
+        render_state
+            .render_world
+            .assign_components((component.get_data(),), *render_entity)
+            .unwrap();
+    }
+
+    state.last_sync = this_sync;
 }
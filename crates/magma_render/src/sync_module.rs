@@ -4,11 +4,13 @@ use feufeu::RenderState;
 use magma_app::{
     World,
     entities::Entity,
-    module::Module,
+    hook::HookKind,
+    module::{Dependencies, Module},
     rayon::iter::{IntoParallelRefIterator, ParallelIterator},
+    tracing::info_span,
 };
 
-use crate::SyncSchedule;
+use crate::{RenderModule, SyncSchedule};
 
 /// Module for syncing entities to the render world.
 pub struct SyncModule;
@@ -29,6 +31,14 @@ impl Module for SyncModule {
         app.world
             .add_resource(EntityRenderEntityMap::new())
             .unwrap();
+        app.world.add_resource(UnsyncedEntities::default()).unwrap();
+        app.add_component_hook::<SyncToRenderWorld>(HookKind::OnRemove, |world, entity| {
+            world
+                .get_resource_mut::<UnsyncedEntities>()
+                .unwrap()
+                .0
+                .push(entity)
+        });
         app.world.add_resource(SyncSystems::new()).unwrap();
 
         app.world
@@ -37,48 +47,54 @@ impl Module for SyncModule {
             .render_world
             .register_component::<RenderEntity>();
     }
+
+    fn dependencies(&self, dependencies: &mut Dependencies) {
+        dependencies.require::<RenderModule>();
+    }
 }
 
 fn sync_entities(world: &World) {
+    let _span = info_span!("sync_entities").entered();
     let mut map = world.get_resource_mut::<EntityRenderEntityMap>().unwrap();
     let render_state = world.get_resource::<RenderState>().unwrap();
 
-    // Sync new entities to render world.
-    world
-        .query::<(SyncToRenderWorld,)>()
-        .unwrap()
-        .iter()
-        .for_each(|entity| {
-            if !map.entity_to_render_entity.contains_key(&entity.into()) {
-                let render_entity = render_state
-                    .render_world
-                    .create_entity((RenderEntity,))
-                    .unwrap();
-
-                map.insert(entity.into(), render_entity);
-            }
-        });
+    // Sync entities, which are no longer synced, before their ids can be reused. Entities are reported by a component hook,
+    // when they are removed through commands, and by the world otherwise.
+    let mut unsynced = std::mem::take(&mut world.get_resource_mut::<UnsyncedEntities>().unwrap().0);
+    unsynced.extend(
+        world
+            .query_removed::<(SyncToRenderWorld,)>()
+            .unwrap()
+            .iter()
+            .map(Entity::from),
+    );
+    for entity in unsynced {
+        if let Some(render_entity) = map.entity_to_render_entity.get(&entity).copied() {
+            map.delete_through_render_entity(&render_entity);
+            // the render entities of windows are already deleted when they close
+            let _ = render_state.render_world.delete_entity(render_entity);
+        }
+    }
 
-    // Sync deleted entities to render world and map.
-    render_state
-        .render_world
-        .query::<(RenderEntity,)>()
-        .unwrap()
-        .iter()
-        .for_each(|render_entity| {
-            let entity = map
-                .render_entity_to_entity
-                .get(&render_entity.into())
-                .unwrap()
-                .to_owned();
-            if world.get_component::<SyncToRenderWorld>(entity).is_err() {
-                map.delete_through_render_entity(&render_entity.into());
-                render_entity.delete();
-            }
-        });
+    // Sync new entities to render world.
+    for entity in &world.query::<(SyncToRenderWorld,)>().unwrap() {
+        if !map.entity_to_render_entity.contains_key(&entity.into()) {
+            let render_entity = render_state
+                .render_world
+                .create_entity((RenderEntity,))
+                .unwrap();
+
+            map.insert(entity.into(), render_entity);
+        }
+    }
 }
 
+/// Entities, which are no longer synced to the render world. Filled by a component hook on [`SyncToRenderWorld`].
+#[derive(Default)]
+struct UnsyncedEntities(Vec<Entity>);
+
 fn sync_systems(world: &World) {
+    let _span = info_span!("sync_systems").entered();
     world
         .get_resource::<SyncSystems>()
         .unwrap()
@@ -87,8 +103,11 @@ fn sync_systems(world: &World) {
         .for_each(|f| f(world));
 }
 
+/// A function syncing data to the render world.
+type SyncSystem = Box<dyn Fn(&World) + Send + Sync>;
+
 pub(crate) struct SyncSystems {
-    systems: Vec<fn(&World)>,
+    systems: Vec<SyncSystem>,
 }
 
 impl SyncSystems {
@@ -96,8 +115,8 @@ impl SyncSystems {
         Self { systems: vec![] }
     }
 
-    pub fn push(&mut self, system: fn(&World)) {
-        self.systems.push(system);
+    pub fn push(&mut self, system: impl Fn(&World) + Send + Sync + 'static) {
+        self.systems.push(Box::new(system));
     }
 }
 
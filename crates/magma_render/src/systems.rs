@@ -1,27 +1,30 @@
 use feufeu::RenderState;
-use magma_app::World;
-use magma_windowing::{ClosingWindow, window_event::WindowClosed};
+use magma_app::{World, entities::Entity, tracing::warn};
+use magma_windowing::ClosingWindow;
 
-use crate::extracted_windows::ExtractedWindows;
+use crate::sync_module::EntityRenderEntityMap;
 
-pub fn drop_windows(world: &World) {
-    let render_state = world.get_resource::<RenderState>().unwrap();
-
-    world
-        .poll_events::<WindowClosed>()
+/// Component hook marking the render entity of a removed window for closing.
+pub fn drop_window(world: &World, window: Entity) {
+    let Some(render_entity) = world
+        .get_resource::<EntityRenderEntityMap>()
+        .unwrap()
+        .entity_to_render_entity
+        .get(&window)
+        .copied()
+    else {
+        warn!(
+            "failed to drop window {}: it has no render entity",
+            window.id()
+        );
+        return;
+    };
+    if let Err(error) = world
+        .get_resource::<RenderState>()
         .unwrap()
-        .iter()
-        .for_each(|window_event| {
-            render_state
-                .render_world
-                .assign_components(
-                    (ClosingWindow,),
-                    render_state
-                        .render_world
-                        .get_resource::<ExtractedWindows>()
-                        .unwrap()
-                        .get_render_entity(&window_event.window),
-                )
-                .unwrap();
-        });
+        .render_world
+        .assign_components((ClosingWindow,), render_entity)
+    {
+        warn!("failed to drop window {}: {error}", window.id());
+    }
 }
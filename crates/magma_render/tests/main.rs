@@ -1,4 +1,4 @@
-use magma_app::{App, World, module::Module, schedule::Update};
+use magma_app::{App, AppExit, World, module::Module, schedule::Update};
 use magma_render::RenderModule;
 use magma_windowing::Window;
 
@@ -28,11 +28,7 @@ fn count_exit(world: &World) {
     let mut counter = world.get_resource_mut::<u32>().unwrap();
 
     if *counter >= 1000 {
-        world
-            .query::<(Window,)>()
-            .unwrap()
-            .iter()
-            .for_each(|w| w.delete());
+        world.send_event(AppExit::Success).unwrap();
     } else {
         *counter += 1;
     }
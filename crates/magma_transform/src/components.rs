@@ -0,0 +1,188 @@
+use std::ops::Mul;
+
+use magma_app::entities::Entity;
+use magma_math::{Affine3A, Mat3, Mat4, Quat, Vec3};
+
+/// The position, rotation and scale of an entity relative to its parent. Entities without a parent are relative to the world.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Transform {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    /// A [`Transform`], which doesn't translate, rotate or scale.
+    pub const IDENTITY: Self = Self {
+        translation: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+        scale: Vec3::ONE,
+    };
+
+    /// Create a [`Transform`] from a translation.
+    pub const fn from_translation(translation: Vec3) -> Self {
+        Self {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Create a [`Transform`] from the coordinates of a translation.
+    pub const fn from_xyz(x: f32, y: f32, z: f32) -> Self {
+        Self::from_translation(Vec3::new(x, y, z))
+    }
+
+    /// Create a [`Transform`] from a rotation.
+    pub const fn from_rotation(rotation: Quat) -> Self {
+        Self {
+            rotation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Create a [`Transform`] from a scale.
+    pub const fn from_scale(scale: Vec3) -> Self {
+        Self {
+            scale,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Create a [`Transform`] from an affine transformation. Shear is lost.
+    pub fn from_affine(affine: Affine3A) -> Self {
+        let (scale, rotation, translation) = affine.to_scale_rotation_translation();
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Set the translation.
+    pub const fn with_translation(mut self, translation: Vec3) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    /// Set the rotation.
+    pub const fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set the scale.
+    pub const fn with_scale(mut self, scale: Vec3) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Rotate the [`Transform`], so its forward direction (-Z) points at `target` and its up direction is as close to `up` as possible.
+    pub fn looking_at(mut self, target: Vec3, up: Vec3) -> Self {
+        let forward = (target - self.translation).normalize();
+        let right = up.cross(-forward).normalize();
+        let up = (-forward).cross(right);
+        self.rotation = Quat::from_mat3(&Mat3::from_cols(right, up, -forward));
+        self
+    }
+
+    /// Get the affine transformation of the [`Transform`].
+    pub fn compute_affine(&self) -> Affine3A {
+        Affine3A::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+
+    /// Get the transformation matrix of the [`Transform`].
+    pub fn compute_matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+
+    /// Transform a point from the local space of the [`Transform`] into the space of its parent.
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.rotation * (self.scale * point) + self.translation
+    }
+}
+
+/**
+The world-space transformation of an entity. It is computed from the [`Transform`]s of the entity and its ancestors by the [`TransformModule`](crate::TransformModule)
+and shouldn't be changed manually.
+
+It also caches the inputs of the last computation, so unchanged subtrees can be skipped.
+*/
+#[derive(Clone, Copy, Debug)]
+pub struct GlobalTransform {
+    pub(crate) affine: Affine3A,
+    /// the local transform and parent the affine has been computed from
+    pub(crate) source: Option<(Transform, Option<Entity>)>,
+}
+
+impl Default for GlobalTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl PartialEq for GlobalTransform {
+    fn eq(&self, other: &Self) -> bool {
+        self.affine == other.affine
+    }
+}
+
+impl From<Transform> for GlobalTransform {
+    fn from(transform: Transform) -> Self {
+        Self::from_affine(transform.compute_affine())
+    }
+}
+
+impl Mul<Transform> for GlobalTransform {
+    type Output = GlobalTransform;
+
+    fn mul(self, transform: Transform) -> Self::Output {
+        Self::from_affine(self.affine * transform.compute_affine())
+    }
+}
+
+impl GlobalTransform {
+    /// A [`GlobalTransform`], which doesn't translate, rotate or scale.
+    pub const IDENTITY: Self = Self {
+        affine: Affine3A::IDENTITY,
+        source: None,
+    };
+
+    /// Create a [`GlobalTransform`] from an affine transformation.
+    pub const fn from_affine(affine: Affine3A) -> Self {
+        Self {
+            affine,
+            source: None,
+        }
+    }
+
+    /// Get the affine transformation.
+    pub fn affine(&self) -> Affine3A {
+        self.affine
+    }
+
+    /// Get the world-space transformation matrix.
+    pub fn compute_matrix(&self) -> Mat4 {
+        Mat4::from(self.affine)
+    }
+
+    /// Get the world-space translation.
+    pub fn translation(&self) -> Vec3 {
+        self.affine.translation.into()
+    }
+
+    /// Get the world-space scale, rotation and translation as a [`Transform`].
+    pub fn compute_transform(&self) -> Transform {
+        Transform::from_affine(self.affine)
+    }
+
+    /// Transform a point from local space into world space.
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.affine.transform_point3(point)
+    }
+}
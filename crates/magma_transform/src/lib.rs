@@ -0,0 +1,49 @@
+/*!
+This crate provides the [`Transform`] and [`GlobalTransform`] components and the [`TransformModule`], which propagates transforms through the entity [`hierarchy`](magma_app::hierarchy).
+*/
+
+use magma_app::{App, module::Module, schedule::PostUpdate};
+
+pub use components::{GlobalTransform, Transform};
+
+mod components;
+mod systems;
+
+/// The name of the system propagating [`Transform`]s in the [`PostUpdate`] schedule. Systems can be ordered relative to it.
+pub const PROPAGATE_TRANSFORMS: &str = "transform_propagate";
+
+/**
+Module computing the [`GlobalTransform`] of every entity from its [`Transform`] and the [`GlobalTransform`] of its parent in the [`PostUpdate`] schedule.
+
+Entities need both components. Subtrees, whose [`Transform`]s and parents haven't changed since the last propagation, aren't recomputed.
+Children without a [`Transform`] and their descendants are ignored.
+
+# Example
+
+```
+use magma_app::App;
+use magma_transform::{GlobalTransform, Transform, TransformModule};
+
+let mut app = App::new();
+app.add_module(TransformModule);
+app.world
+    .create_entity((Transform::from_xyz(1.0, 2.0, 3.0), GlobalTransform::default()))
+    .unwrap();
+app.update();
+```
+*/
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct TransformModule;
+
+impl Module for TransformModule {
+    fn setup(self, app: &mut App) {
+        app.world.register_component::<Transform>();
+        app.world.register_component::<GlobalTransform>();
+        app.add_systems::<PostUpdate>(&[(
+            systems::propagate_transforms,
+            PROPAGATE_TRANSFORMS,
+            &[],
+        )])
+        .unwrap();
+    }
+}
@@ -0,0 +1,56 @@
+use magma_app::{
+    World,
+    entities::Entity,
+    hierarchy::{Parent, children_of},
+    rayon::iter::{IntoParallelRefIterator, ParallelIterator},
+};
+use magma_math::Affine3A;
+
+use crate::{GlobalTransform, Transform};
+
+pub fn propagate_transforms(world: &World) {
+    world
+        .query::<(Transform, GlobalTransform)>()
+        .unwrap()
+        .par_iter()
+        .filter(|entity| entity.get_component::<Parent>().is_err())
+        .for_each(|root| propagate(world, root.into(), None, false));
+}
+
+/// Recompute the [`GlobalTransform`] of `entity`, if it or one of its ancestors changed, and continue with its children.
+fn propagate(
+    world: &World,
+    entity: Entity,
+    parent: Option<(Entity, Affine3A)>,
+    parent_changed: bool,
+) {
+    let Ok(transform) = world
+        .get_component::<Transform>(entity)
+        .map(|transform| *transform)
+    else {
+        return;
+    };
+    let Ok(global_transform) = world
+        .get_component::<GlobalTransform>(entity)
+        .map(|global_transform| *global_transform)
+    else {
+        return;
+    };
+
+    let source = Some((transform, parent.map(|(parent, _)| parent)));
+    let changed = parent_changed || global_transform.source != source;
+    let mut affine = global_transform.affine;
+    if changed {
+        let parent_affine = parent.map_or(Affine3A::IDENTITY, |(_, affine)| affine);
+        affine = parent_affine * transform.compute_affine();
+        // mutable access marks the component as changed, so it is only taken, when it is recomputed
+        if let Ok(mut global_transform) = world.get_component_mut::<GlobalTransform>(entity) {
+            global_transform.affine = affine;
+            global_transform.source = source;
+        }
+    }
+
+    for child in children_of(world, entity) {
+        propagate(world, child, Some((entity, affine)), changed);
+    }
+}
@@ -0,0 +1,124 @@
+use magma_app::{App, hierarchy::set_parent};
+use magma_math::{Quat, Vec3};
+use magma_transform::{GlobalTransform, Transform, TransformModule};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_module(TransformModule);
+    app
+}
+
+fn approx_eq(a: Vec3, b: Vec3) -> bool {
+    a.abs_diff_eq(b, 1e-5)
+}
+
+#[test]
+fn transform_conversions() {
+    let transform = Transform::from_xyz(1.0, 2.0, 3.0)
+        .with_rotation(Quat::from_rotation_y(1.0))
+        .with_scale(Vec3::splat(2.0));
+    let global = GlobalTransform::from(transform);
+
+    assert!(approx_eq(
+        global.transform_point(Vec3::X),
+        transform.transform_point(Vec3::X)
+    ));
+    assert!(approx_eq(global.translation(), transform.translation));
+    assert!(approx_eq(global.compute_transform().scale, transform.scale));
+    assert!(approx_eq(
+        Transform::IDENTITY.looking_at(Vec3::X, Vec3::Y).rotation * Vec3::NEG_Z,
+        Vec3::X
+    ));
+}
+
+#[test]
+fn propagate_through_hierarchy() {
+    let app = app();
+    let root = app
+        .world
+        .create_entity((
+            Transform::from_xyz(1.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
+            GlobalTransform::default(),
+        ))
+        .unwrap();
+    let child = app
+        .world
+        .create_entity((
+            Transform::from_xyz(0.0, 1.0, 0.0),
+            GlobalTransform::default(),
+        ))
+        .unwrap();
+    let grandchild = app
+        .world
+        .create_entity((
+            Transform::from_xyz(0.0, 0.0, 1.0),
+            GlobalTransform::default(),
+        ))
+        .unwrap();
+    set_parent(&app.world, child, root).unwrap();
+    set_parent(&app.world, grandchild, child).unwrap();
+
+    app.update();
+    let translation = |entity| {
+        app.world
+            .get_component::<GlobalTransform>(entity)
+            .unwrap()
+            .translation()
+    };
+    assert!(approx_eq(translation(root), Vec3::new(1.0, 0.0, 0.0)));
+    assert!(approx_eq(translation(child), Vec3::new(1.0, 2.0, 0.0)));
+    assert!(approx_eq(translation(grandchild), Vec3::new(1.0, 2.0, 2.0)));
+
+    // changing an ancestor updates the whole subtree
+    app.world
+        .get_component_mut::<Transform>(root)
+        .unwrap()
+        .scale = Vec3::ONE;
+    app.update();
+    assert!(approx_eq(translation(child), Vec3::new(1.0, 1.0, 0.0)));
+    assert!(approx_eq(translation(grandchild), Vec3::new(1.0, 1.0, 1.0)));
+
+    // changing a child doesn't affect its parent
+    app.world
+        .get_component_mut::<Transform>(child)
+        .unwrap()
+        .translation = Vec3::ZERO;
+    app.update();
+    assert!(approx_eq(translation(root), Vec3::new(1.0, 0.0, 0.0)));
+    assert!(approx_eq(translation(grandchild), Vec3::new(1.0, 0.0, 1.0)));
+}
+
+#[test]
+fn reparenting_recomputes() {
+    let app = app();
+    let first = app
+        .world
+        .create_entity((
+            Transform::from_xyz(5.0, 0.0, 0.0),
+            GlobalTransform::default(),
+        ))
+        .unwrap();
+    let second = app
+        .world
+        .create_entity((
+            Transform::from_xyz(0.0, 5.0, 0.0),
+            GlobalTransform::default(),
+        ))
+        .unwrap();
+    let child = app
+        .world
+        .create_entity((Transform::IDENTITY, GlobalTransform::default()))
+        .unwrap();
+    set_parent(&app.world, child, first).unwrap();
+    app.update();
+
+    set_parent(&app.world, child, second).unwrap();
+    app.update();
+    assert!(approx_eq(
+        app.world
+            .get_component::<GlobalTransform>(child)
+            .unwrap()
+            .translation(),
+        Vec3::new(0.0, 5.0, 0.0)
+    ));
+}
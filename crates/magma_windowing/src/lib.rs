@@ -13,6 +13,7 @@ pub mod window;
 pub mod window_event;
 
 /// The Window module for the App
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 pub struct WindowingModule;
 
 impl Module for WindowingModule {
@@ -20,10 +21,12 @@ impl Module for WindowingModule {
         app.world.register_component::<Monitor>();
         app.world.register_component::<PrimaryMonitor>();
         app.world.register_component::<Window>();
+        app.track_changes::<Window>();
         app.world.register_component::<ClosingWindow>();
 
         app.register_event::<WindowResized>();
         app.register_event::<RedrawRequested>();
+        app.register_event::<RequestRedraw>();
         app.register_event::<WindowCreated>();
         app.register_event::<WindowCloseRequested>();
         app.register_event::<WindowClosed>();
@@ -36,5 +39,9 @@ impl Module for WindowingModule {
         app.register_event::<FileDragDrop>();
         app.register_event::<WindowMoved>();
         app.register_event::<WindowThemeChanged>();
+
+        // cursor positions are relative to the window size
+        app.order_event_systems::<WindowResized, CursorMoved>()
+            .unwrap();
     }
 }
@@ -3,7 +3,7 @@ use std::num::NonZero;
 use magma_app::entities::Entity;
 use magma_math::{IVec2, UVec2};
 
-/// The Window Component
+/// The Window Component. Its changes are tracked, see [`Changed`](magma_app::change::Changed).
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Window {
     title: String,
@@ -27,8 +27,6 @@ pub struct Window {
 
     /// True if the backend has created a window for this component.
     pub has_window: bool,
-    /// True if this component was modified in the current update. This does not include changes that the backend makesto sync windows.
-    pub changed_attr: bool,
 }
 
 impl Default for Window {
@@ -53,7 +51,6 @@ impl Default for Window {
             window_theme: Default::default(),
             desired_maximum_frame_latency: NonZero::new(2_u32),
             has_window: false,
-            changed_attr: false,
         }
     }
 }
@@ -77,7 +74,6 @@ impl Window {
     /// Set the window's display title.
     pub fn set_title(&mut self, title: &str) {
         self.title = title.to_owned();
-        self.changed_attr = true;
     }
 
     /// Create the window with a custom (optional) name.
@@ -94,7 +90,6 @@ impl Window {
     /// Set the window's optional name. This can only be set after first creating the window.
     pub fn set_name(&mut self, name: &str) {
         self.name = Some(name.to_owned());
-        self.changed_attr = true;
     }
 
     /// Create the window with specified [`WindowPosition`].
@@ -111,7 +106,6 @@ impl Window {
     /// Set the [`WindowPosition`].
     pub fn set_position(&mut self, position: WindowPosition) {
         self.position = position;
-        self.changed_attr = true;
     }
 
     /// Create the window with specified [`WindowResolution`].
@@ -128,7 +122,6 @@ impl Window {
     /// Set the [`WindowResolution`].
     pub fn set_resolution(&mut self, resolution: WindowResolution) {
         self.resolution = resolution;
-        self.changed_attr = true;
     }
 
     /// Set if the window should be resizable on creation.
@@ -145,7 +138,6 @@ impl Window {
     /// Set if the window should be resizable.
     pub fn set_resizable(&mut self, resizable: bool) {
         self.resizable = resizable;
-        self.changed_attr = true;
     }
 
     /// Create the window with specified [`WindowResizeLimit`].
@@ -162,7 +154,6 @@ impl Window {
     /// Set the [`WindowResizeLimit`].
     pub fn set_resize_limit(&mut self, resize_limit: WindowResizeLimit) {
         self.resize_limit = resize_limit;
-        self.changed_attr = true;
     }
 
     /// Create the window with specified [`WindowMode`].
@@ -179,7 +170,6 @@ impl Window {
     /// Set the [`WindowMode`].
     pub fn set_mode(&mut self, mode: WindowMode) {
         self.mode = mode;
-        self.changed_attr = true;
     }
 
     /// Create the window with specified [`CursorMode`].
@@ -196,7 +186,6 @@ impl Window {
     /// Set the [`CursorMode`].
     pub fn set_cursor_mode(&mut self, cursor_mode: CursorMode) {
         self.cursor_mode = cursor_mode;
-        self.changed_attr = true;
     }
 
     /// Set if the cursor should be visible at window creation.
@@ -213,7 +202,6 @@ impl Window {
     /// Set if the cursor should be visible.
     pub fn set_cursor_visible(&mut self, cursor_visible: bool) {
         self.cursor_visible = cursor_visible;
-        self.changed_attr = true;
     }
 
     /// Set if window decorations should be enabled at window creation.
@@ -230,7 +218,6 @@ impl Window {
     /// Set if window decorations should be enabled.
     pub fn set_decorations(&mut self, decorations: bool) {
         self.decorations = decorations;
-        self.changed_attr = true;
     }
 
     /// Set which [`TitlebarButtons`] should be enabled at window creation.
@@ -247,7 +234,6 @@ impl Window {
     /// Set which [`TitlebarButtons`] should be enabled.
     pub fn set_titlebar_buttons(&mut self, titlebar_buttons: TitlebarButtons) {
         self.titlebar_buttons = titlebar_buttons;
-        self.changed_attr = true;
     }
 
     /// Create the window with specified [`PresentMode`].
@@ -264,7 +250,6 @@ impl Window {
     /// Set the [`PresentMode`].
     pub fn set_present_mode(&mut self, present_mode: PresentMode) {
         self.present_mode = present_mode;
-        self.changed_attr = true;
     }
 
     /// Create the Window with specified [`AlphaMode`].
@@ -281,7 +266,6 @@ impl Window {
     /// Set the [`AlphaMode`].
     pub fn set_alpha_mode(&mut self, alpha_mode: AlphaMode) {
         self.alpha_mode = alpha_mode;
-        self.changed_attr = true;
     }
 
     /// Set if the window should be transparent at window creation.
@@ -298,7 +282,6 @@ impl Window {
     /// Set if the window should be transparent.
     pub fn set_transparent(&mut self, transparent: bool) {
         self.transparent = transparent;
-        self.changed_attr = true;
     }
 
     /// Create the window with specified focus.
@@ -315,7 +298,6 @@ impl Window {
     /// Set if the window should be focused.
     pub fn set_focused(&mut self, focused: bool) {
         self.focused = focused;
-        self.changed_attr = true;
     }
 
     /// Set if default event handling should be enabled at window creation.
@@ -332,7 +314,6 @@ impl Window {
     /// Set if default event handling should be enabled.
     pub fn set_default_event_handling(&mut self, default_event_handling: bool) {
         self.default_event_handling = default_event_handling;
-        self.changed_attr = true;
     }
 
     /// Create the window with specified [`WindowTheme`].
@@ -349,7 +330,6 @@ impl Window {
     /// Set the [`WindowTheme`].
     pub fn set_window_theme(&mut self, window_theme: WindowTheme) {
         self.window_theme = window_theme;
-        self.changed_attr = true;
     }
 
     /// Create the window disired maximum frame latency.
@@ -372,7 +352,6 @@ impl Window {
         desired_maximum_frame_latency: Option<NonZero<u32>>,
     ) {
         self.desired_maximum_frame_latency = desired_maximum_frame_latency;
-        self.changed_attr = true;
     }
 }
 
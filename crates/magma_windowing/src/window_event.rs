@@ -17,6 +17,10 @@ pub struct WindowResized {
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 pub struct RedrawRequested;
 
+/// An event, which can be sent by systems to request another update, when the app is in a reactive update mode.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct RequestRedraw;
+
 /// An event signaling a window has been created.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 pub struct WindowCreated {
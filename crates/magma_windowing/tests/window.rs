@@ -1,4 +1,7 @@
-use magma_app::App;
+use magma_app::{
+    App,
+    change::{Changed, Tick},
+};
 use magma_math::IVec2;
 use magma_windowing::{window_event::*, *};
 
@@ -8,18 +11,17 @@ fn create_window() {
     app.add_module(WindowingModule);
 
     let window = app.world.create_entity((Window::new(),)).unwrap();
+    app.apply_commands();
+    let created = Tick::now(&app.world);
+    assert!(!Changed::<Window>::matches(&app.world, window, created).unwrap());
+
     app.world
         .get_component_mut::<Window>(window)
         .unwrap()
         .set_title("Hello World!");
+    app.apply_commands();
 
-    assert_eq!(
-        app.world
-            .get_component::<Window>(window)
-            .unwrap()
-            .changed_attr,
-        true
-    );
+    assert!(Changed::<Window>::matches(&app.world, window, created).unwrap());
 }
 
 #[test]
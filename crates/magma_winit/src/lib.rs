@@ -10,7 +10,7 @@ This crate integrates [`winit`] into the Magma API in order to manage applicatio
 # use magma_winit::WinitModule;
 fn main() -> Result<(), Box<dyn Error>> {
     let mut app = App::new();
-    app.add_module(WinitModule);
+    app.add_module(WinitModule::default());
     // Add the system to close created windows.
     // Windows should not be closed in a startup system, bc it might cause the app to hang.
     app.add_systems::<Update>(&[(close_windows, "close_windows", &[])]).unwrap();
@@ -33,8 +33,16 @@ fn close_windows(world: &World) {
 ```
 */
 
-use magma_app::schedule::{PostUpdate, PreUpdate, Startup, Update};
-use magma_app::{App, module::Module};
+use std::time::Instant;
+
+use magma_app::change::{Changed, Tick};
+use magma_app::event::{EventReader, Events};
+use magma_app::schedule::PreUpdate;
+use magma_app::tracing::{debug, info, info_span};
+use magma_app::{
+    App, AppExit,
+    module::{Dependencies, Module},
+};
 use magma_input::InputModule;
 use magma_input::input_event::{
     KeyboardInput, MouseButtonInput, MouseMotionInput, MouseScrollInput,
@@ -44,6 +52,7 @@ use magma_windowing::monitor::VideoMode;
 use magma_windowing::window::WindowTheme;
 use magma_windowing::{Monitor, PrimaryMonitor, window_event::*};
 use magma_windowing::{Window, WindowingModule};
+use settings::{UpdateMode, WinitSettings};
 use windows::Windows;
 use winit::event_loop::ActiveEventLoop;
 use winit::{
@@ -52,21 +61,41 @@ use winit::{
     event_loop::{ControlFlow, EventLoop},
 };
 
+/// Settings for the update frequency of the event loop
+pub mod settings;
 mod systems;
 pub mod windows;
 
 /**
-The [`WinitModule`] adds winit as a backend for [magma_windowing](https://crates.io/crates/magma_windowing). It also automatically creates one window on application start.
+The [`WinitModule`] adds winit as a backend for [magma_windowing](https://crates.io/crates/magma_windowing). By default it also creates one window on application start.
 */
-pub struct WinitModule;
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WinitModule {
+    /// The primary window, which is created on setup. No window is created if this is `None`.
+    pub primary_window: Option<Window>,
+    /// The initial [`WinitSettings`], which are added as a resource.
+    pub settings: WinitSettings,
+}
+
+impl Default for WinitModule {
+    fn default() -> Self {
+        Self {
+            primary_window: Some(Window::new()),
+            settings: WinitSettings::default(),
+        }
+    }
+}
 
 impl Module for WinitModule {
     fn setup(self, app: &mut magma_app::App) {
         app.set_runner(winit_event_loop);
-        app.add_module(WindowingModule);
-        app.add_module(InputModule);
+
+        if let Some(window) = self.primary_window {
+            app.world.create_entity((window,)).unwrap();
+        }
 
         app.world.add_resource(Windows::new()).unwrap();
+        app.world.add_resource(self.settings).unwrap();
 
         app.add_systems::<PreUpdate>(&[(
             systems::delete_pending_windows,
@@ -89,22 +118,109 @@ impl Module for WinitModule {
         app.add_event_systems::<WindowFocused>(&[(systems::focused, "winit_focused", &[])])
             .unwrap();
     }
+
+    fn dependencies(&self, dependencies: &mut Dependencies) {
+        dependencies.add::<WindowingModule>().add::<InputModule>();
+    }
 }
 
 pub struct WrappedApp {
     pub app: App,
+    exit: Option<AppExit>,
+    last_update: Option<Instant>,
+    /// an event has been received since the last update
+    wake: bool,
+    focused: bool,
+    redraw_requests: EventReader<RequestRedraw>,
+    /// window components changed after this tick need to be synced
+    last_window_sync: Tick,
 }
 
 impl WrappedApp {
     pub fn new(app: App) -> Self {
-        Self { app }
+        Self {
+            app,
+            exit: None,
+            last_update: None,
+            wake: false,
+            focused: true,
+            redraw_requests: EventReader::default(),
+            last_window_sync: Tick::default(),
+        }
+    }
+
+    /**
+    Returns `true` if the [`App`] should be updated now according to the [`UpdateMode`] in the [`WinitSettings`].
+
+    If it returns `false`, the control flow of the event loop is set to wait until the next update.
+    */
+    pub fn should_update(&mut self, event_loop: &ActiveEventLoop) -> bool {
+        let now = Instant::now();
+        let due = match (self.update_mode(), self.last_update) {
+            (_, None) | (UpdateMode::Continuous, _) => true,
+            (UpdateMode::Limited { wait }, Some(last_update)) => now >= last_update + wait,
+            (UpdateMode::Reactive { wait }, Some(last_update)) => {
+                self.wake || now >= last_update + wait
+            }
+        };
+        if due {
+            self.wake = false;
+            self.last_update = Some(now);
+        } else {
+            self.set_control_flow(event_loop);
+        }
+        due
+    }
+
+    /// Handle [`RequestRedraw`] events and set the control flow of the event loop. Call this after the [`App`] has been updated.
+    pub fn updated(&mut self, event_loop: &ActiveEventLoop) {
+        let redraw_requested = self
+            .app
+            .world
+            .get_resource::<Events<RequestRedraw>>()
+            .is_ok_and(|requests| self.redraw_requests.read(&requests).next().is_some());
+        self.wake |= redraw_requested;
+        self.set_control_flow(event_loop);
+    }
+
+    fn update_mode(&self) -> UpdateMode {
+        self.app
+            .world
+            .get_resource::<WinitSettings>()
+            .map(|settings| settings.update_mode(self.focused))
+            .unwrap_or(UpdateMode::Continuous)
+    }
+
+    fn set_control_flow(&self, event_loop: &ActiveEventLoop) {
+        let control_flow = match (self.update_mode(), self.last_update) {
+            (UpdateMode::Continuous, _) | (_, None) => ControlFlow::Poll,
+            (UpdateMode::Reactive { .. }, _) if self.wake => ControlFlow::Poll,
+            (UpdateMode::Limited { wait } | UpdateMode::Reactive { wait }, Some(last_update)) => {
+                ControlFlow::WaitUntil(last_update + wait)
+            }
+        };
+        event_loop.set_control_flow(control_flow);
+    }
+
+    /// Exit the event loop with the specified [`AppExit`].
+    pub fn exit(&mut self, event_loop: &ActiveEventLoop, exit: AppExit) {
+        self.exit = Some(exit);
+        event_loop.exit();
+    }
+
+    /// Returns the [`AppExit`] the event loop has been exited with.
+    pub fn exit_status(&self) -> Option<AppExit> {
+        self.exit
     }
 }
 
 impl ApplicationHandler for WrappedApp {
     fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        let _span = info_span!("enumerate_monitors").entered();
         let primary_monitor = event_loop.primary_monitor();
+        let mut count = 0;
         for (id, winit_monitor) in event_loop.available_monitors().enumerate() {
+            count += 1;
             let monitor = Monitor {
                 name: winit_monitor.name(),
                 height: winit_monitor.size().height,
@@ -125,6 +241,15 @@ impl ApplicationHandler for WrappedApp {
                     .collect(),
                 id,
             };
+            debug!(
+                id,
+                name = ?monitor.name,
+                width = monitor.width,
+                height = monitor.height,
+                refresh_rate = ?monitor.refresh_rate,
+                scale_factor = monitor.scale_factor,
+                "found monitor"
+            );
             if primary_monitor.as_ref() == Some(&winit_monitor) {
                 self.app
                     .world
@@ -134,6 +259,7 @@ impl ApplicationHandler for WrappedApp {
                 self.app.world.create_entity((monitor,)).unwrap();
             }
         }
+        info!(count, "enumerated monitors");
     }
 
     fn window_event(
@@ -142,6 +268,10 @@ impl ApplicationHandler for WrappedApp {
         window_id: winit::window::WindowId,
         event: WindowEvent,
     ) {
+        self.wake = true;
+        if let WindowEvent::Focused(focused) = &event {
+            self.focused = *focused;
+        }
         // convert winit events to app events
         match event {
             WindowEvent::Resized(physical_size) => self
@@ -1606,6 +1736,7 @@ impl ApplicationHandler for WrappedApp {
         _device_id: winit::event::DeviceId,
         event: winit::event::DeviceEvent,
     ) {
+        self.wake = true;
         match event {
             winit::event::DeviceEvent::MouseMotion { delta } => self
                 .app
@@ -1619,27 +1750,32 @@ impl ApplicationHandler for WrappedApp {
     }
 
     fn about_to_wait(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        if self.exit.is_some() || !self.should_update(event_loop) {
+            return;
+        }
         // update winit backend
         self.winit_update(event_loop);
         // update the app
-        self.app.run_schedule::<PreUpdate>().unwrap();
-        self.app.run_schedule::<Update>().unwrap();
-        self.app.run_schedule::<PostUpdate>().unwrap();
-        self.app.process_events();
+        if let Some(exit) = self.app.update() {
+            self.exit(event_loop, exit);
+        }
+        self.updated(event_loop);
     }
 }
 
 impl WrappedApp {
     pub fn winit_update(&mut self, event_loop: &ActiveEventLoop) {
-        // create winit windows for new window components
+        // create winit windows for new window components and sync changed ones
+        let changed_windows =
+            Changed::<Window>::since(&self.app.world, self.last_window_sync).unwrap_or_default();
         self.app
             .world
             .query::<(Window,)>()
             .unwrap()
             .iter()
             .for_each(|window_entity| {
-                let mut window_component = window_entity.get_component_mut::<Window>().unwrap();
-                if !window_component.has_window {
+                let has_window = window_entity.get_component::<Window>().unwrap().has_window;
+                if !has_window {
                     self.app
                         .world
                         .get_resource_mut::<Windows>()
@@ -1647,34 +1783,36 @@ impl WrappedApp {
                         .create_winit_window(
                             &self.app.world,
                             event_loop,
-                            &mut window_component,
+                            &mut window_entity.get_component_mut::<Window>().unwrap(),
                             window_entity.into(),
                         );
-                } else if window_component.changed_attr {
+                    self.app
+                        .world
+                        .send_event(WindowCreated {
+                            window: window_entity.into(),
+                        })
+                        .unwrap();
+                } else if changed_windows.contains(&window_entity.into()) {
                     self.app
                         .world
                         .get_resource_mut::<Windows>()
                         .unwrap()
                         .update_winit_window(
-                            &mut window_component,
+                            &mut window_entity.get_component_mut::<Window>().unwrap(),
                             window_entity.into(),
                             &self.app.world,
                         );
                 }
-                window_component.changed_attr = false;
-                self.app
-                    .world
-                    .send_event(WindowCreated {
-                        window: window_entity.into(),
-                    })
-                    .unwrap();
             });
+        // record the changes made by the backend above, so they aren't synced back
+        self.app.apply_commands();
+        self.last_window_sync = Tick::now(&self.app.world);
 
         let windows = self.app.world.query::<(Window,)>().unwrap();
 
         // exit if no windows are present
         if windows.is_empty() {
-            event_loop.exit();
+            self.app.world.send_event(AppExit::Success).unwrap();
         } else if windows.len()
             < self
                 .app
@@ -1715,13 +1853,12 @@ impl WrappedApp {
     }
 }
 
-fn winit_event_loop(app: App) {
-    // create primary window
-    app.world.create_entity((Window::new(),)).unwrap();
+fn winit_event_loop(app: App) -> AppExit {
     // set up winit event loop
     let event_loop = EventLoop::new().unwrap();
-    event_loop.set_control_flow(ControlFlow::Poll);
     let mut app = WrappedApp::new(app);
-    app.app.run_schedule::<Startup>().unwrap();
+    app.app.startup();
     event_loop.run_app(&mut app).unwrap();
+    app.app.shutdown();
+    app.exit_status().unwrap_or_default()
 }
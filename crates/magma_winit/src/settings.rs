@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+/// How often the [`App`](magma_app::App) is updated by the winit event loop.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum UpdateMode {
+    /// Update as often as possible.
+    Continuous,
+    /// Update at most once every `wait`, e.g. to cap the frame rate.
+    Limited { wait: Duration },
+    /**
+    Only update when a window or input event has been received,
+    a [`RequestRedraw`](magma_windowing::window_event::RequestRedraw) event has been sent or `wait` has passed since the last update.
+    */
+    Reactive { wait: Duration },
+}
+
+impl UpdateMode {
+    /// Update at most `fps` times per second.
+    /// Returns `None`, if the rate isn't positive or doesn't result in a representable, non-zero frame time.
+    pub fn from_fps(fps: f64) -> Option<Self> {
+        Duration::try_from_secs_f64(fps.recip())
+            .ok()
+            .filter(|wait| fps > 0.0 && !wait.is_zero())
+            .map(|wait| Self::Limited { wait })
+    }
+}
+
+/**
+Resource containing the [`UpdateMode`]s of the winit event loop. It is added by the [`WinitModule`](crate::WinitModule) and can be changed at any time.
+
+# Example
+
+```
+use std::time::Duration;
+use magma_app::App;
+use magma_winit::{WinitModule, settings::{UpdateMode, WinitSettings}};
+
+let mut app = App::new();
+app.add_module(WinitModule {
+    settings: WinitSettings {
+        focused_mode: UpdateMode::from_fps(60.0).unwrap(),
+        unfocused_mode: UpdateMode::Reactive {
+            wait: Duration::from_secs(1),
+        },
+    },
+    ..Default::default()
+});
+```
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct WinitSettings {
+    /// The [`UpdateMode`] used, while a window is focused.
+    pub focused_mode: UpdateMode,
+    /// The [`UpdateMode`] used, while no window is focused.
+    pub unfocused_mode: UpdateMode,
+}
+
+impl Default for WinitSettings {
+    fn default() -> Self {
+        Self::game()
+    }
+}
+
+impl WinitSettings {
+    /// Settings for games, which update continuously.
+    pub fn game() -> Self {
+        Self {
+            focused_mode: UpdateMode::Continuous,
+            unfocused_mode: UpdateMode::Continuous,
+        }
+    }
+
+    /// Power-saving settings for desktop applications, which only update in reaction to events.
+    pub fn desktop_app() -> Self {
+        Self {
+            focused_mode: UpdateMode::Reactive {
+                wait: Duration::from_secs(5),
+            },
+            unfocused_mode: UpdateMode::Reactive {
+                wait: Duration::from_secs(60),
+            },
+        }
+    }
+
+    /// Get the [`UpdateMode`] depending on whether a window is focused.
+    pub fn update_mode(&self, focused: bool) -> UpdateMode {
+        if focused {
+            self.focused_mode
+        } else {
+            self.unfocused_mode
+        }
+    }
+}
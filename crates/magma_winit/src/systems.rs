@@ -1,14 +1,16 @@
 use magma_app::{
     World,
+    command::Commands,
     rayon::iter::{IntoParallelRefIterator, ParallelIterator},
 };
 use magma_windowing::{
     ClosingWindow, Window,
-    window::WindowResolution,
+    window::{WindowPosition, WindowResolution},
     window_event::{WindowCloseRequested, WindowClosed, WindowFocused, WindowMoved, WindowResized},
 };
 
 pub fn mark_closed_windows(world: &World) {
+    let commands = Commands::new(world);
     let close_requests = world.poll_events::<WindowCloseRequested>().unwrap();
 
     for close_request in close_requests {
@@ -23,7 +25,7 @@ pub fn mark_closed_windows(world: &World) {
                         .unwrap()
                         .default_event_handling()
                 {
-                    window.assign_components((ClosingWindow,)).unwrap();
+                    commands.insert(window.into(), (ClosingWindow,));
                 }
             });
     }
@@ -33,16 +35,16 @@ pub fn resized(world: &World) {
     let resize_events = world.poll_events::<WindowResized>().unwrap();
 
     for resize_event in resize_events {
-        let mut window = world
-            .get_component_mut::<Window>(resize_event.window)
-            .unwrap();
+        let resolution = WindowResolution::new(resize_event.width, resize_event.height);
+        let window = world.get_component::<Window>(resize_event.window).unwrap();
 
-        if window.default_event_handling() {
-            window.set_resolution(WindowResolution::new(
-                resize_event.width,
-                resize_event.height,
-            ));
-            window.changed_attr = false;
+        // mutable access marks the window as changed, so it is only taken, when the value differs
+        if window.default_event_handling() && window.resolution() != resolution {
+            drop(window);
+            world
+                .get_component_mut::<Window>(resize_event.window)
+                .unwrap()
+                .set_resolution(resolution);
         }
     }
 }
@@ -51,15 +53,15 @@ pub fn moved(world: &World) {
     let move_events = world.poll_events::<WindowMoved>().unwrap();
 
     for move_event in move_events {
-        let mut window = world
-            .get_component_mut::<Window>(move_event.window)
-            .unwrap();
+        let position = WindowPosition::Pos(move_event.position);
+        let window = world.get_component::<Window>(move_event.window).unwrap();
 
-        if window.default_event_handling() {
-            window.set_position(magma_windowing::window::WindowPosition::Pos(
-                move_event.position,
-            ));
-            window.changed_attr = false;
+        if window.default_event_handling() && window.position() != position {
+            drop(window);
+            world
+                .get_component_mut::<Window>(move_event.window)
+                .unwrap()
+                .set_position(position);
         }
     }
 }
@@ -68,24 +70,26 @@ pub fn focused(world: &World) {
     let focus_events = world.poll_events::<WindowFocused>().unwrap();
 
     for focus_event in focus_events {
-        let mut window = world
-            .get_component_mut::<Window>(focus_event.window)
-            .unwrap();
+        let window = world.get_component::<Window>(focus_event.window).unwrap();
 
-        if window.default_event_handling() {
-            window.set_focused(focus_event.focus);
-            window.changed_attr = false;
+        if window.default_event_handling() && window.focused() != focus_event.focus {
+            drop(window);
+            world
+                .get_component_mut::<Window>(focus_event.window)
+                .unwrap()
+                .set_focused(focus_event.focus);
         }
     }
 }
 
 pub fn delete_pending_windows(world: &World) {
+    let commands = Commands::new(world);
     world
         .query::<(ClosingWindow, Window)>()
         .unwrap()
         .iter()
         .for_each(|closing_window| {
-            closing_window.delete();
+            commands.despawn(closing_window.into());
             world
                 .send_event(WindowClosed {
                     window: closing_window.into(),
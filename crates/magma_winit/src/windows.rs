@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 
-use magma_app::{World, entities::Entity};
+use magma_app::{
+    World,
+    entities::Entity,
+    tracing::{debug, info, info_span},
+};
 use magma_math::IVec2;
 use magma_windowing::{
     Monitor, PrimaryMonitor, Window,
@@ -44,6 +48,8 @@ impl Windows {
         window: &mut Window,
         entity: Entity,
     ) {
+        let _span =
+            info_span!("create_window", entity = entity.id(), title = %window.title()).entered();
         let mut window_attributes = WinitWindow::default_attributes();
 
         let window_resolution = window.resolution();
@@ -258,6 +264,13 @@ impl Windows {
 
         // add window to self
         let window_id = winit_window.id();
+        info!(
+            ?window_id,
+            width = winit_window.inner_size().width,
+            height = winit_window.inner_size().height,
+            "created window"
+        );
+        debug!(mode = ?window.mode(), cursor_mode = ?window.cursor_mode(), "window settings");
 
         self.winit_windows
             .insert(window_id, WindowWrapper::new(winit_window));
@@ -4,7 +4,7 @@ use magma_winit::WinitModule;
 
 fn main() {
     let mut app = App::new();
-    app.add_module(WinitModule);
+    app.add_module(WinitModule::default());
     app.add_systems::<Update>(&[
         (close_windows, "close_windows", &["open_windows"]),
         (open_windows, "open_windows", &[]),
@@ -5,25 +5,64 @@ pub use magma_math;
 // pub use magma_ui;
 #[cfg(feature = "input")]
 pub use magma_input;
+#[cfg(feature = "log")]
+pub use magma_log;
+#[cfg(feature = "transform")]
+pub use magma_transform;
 #[cfg(feature = "windowing")]
 pub use magma_windowing;
 #[cfg(feature = "winit")]
 pub use magma_winit;
 
-use magma_app::module::Module;
+use magma_app::module::{ModuleGroup, ModuleGroupBuilder};
 // use magma_audio::AudioModule;
 // use magma_winit::WinitModule;
 
+/**
+The default modules of the engine, depending on the enabled cargo features.
+
+# Example
+
+```no_run
+use magma_api::{DefaultModules, magma_app::{App, module::ModuleGroup}};
+# #[cfg(feature = "winit")]
+use magma_api::magma_winit::WinitModule;
+
+let mut app = App::new();
+// don't create a primary window
+# #[cfg(feature = "winit")]
+app.add_modules(DefaultModules.build().set(WinitModule {
+    primary_window: None,
+    ..Default::default()
+}));
+```
+*/
 pub struct DefaultModules;
 
-impl Module for DefaultModules {
-    fn setup(self, app: &mut magma_app::App) {
+impl ModuleGroup for DefaultModules {
+    fn build(self) -> ModuleGroupBuilder {
+        let mut group = ModuleGroupBuilder::new();
+        #[cfg(feature = "log")]
+        {
+            group = group.add_module(magma_log::LogModule::default());
+        }
+        #[cfg(feature = "transform")]
+        {
+            group = group.add_module(magma_transform::TransformModule);
+        }
         #[cfg(feature = "input")]
-        app.add_module(magma_input::InputModule);
+        {
+            group = group.add_module(magma_input::InputModule);
+        }
         #[cfg(feature = "windowing")]
-        app.add_module(magma_windowing::WindowingModule);
+        {
+            group = group.add_module(magma_windowing::WindowingModule);
+        }
         #[cfg(feature = "winit")]
-        app.add_module(magma_winit::WinitModule);
+        {
+            group = group.add_module(magma_winit::WinitModule::default());
+        }
+        group
     }
 }
 
@@ -31,8 +70,16 @@ impl Module for DefaultModules {
 mod tests {
     use magma_app::App;
 
+    use crate::DefaultModules;
+
     #[test]
     fn create_app() {
         let _app = App::new();
     }
+
+    #[test]
+    fn add_default_modules() {
+        let mut app = App::new();
+        app.add_modules(DefaultModules);
+    }
 }